@@ -0,0 +1,397 @@
+use crate::
+{
+  event::
+  {
+    EventSender,
+  },
+  frame::
+  {
+    EditorFrame,
+    PixelFrame,
+    PlotFrame,
+    StatusFrame,
+    TextFrame,
+    style::
+    {
+      Colour,
+      Style,
+    },
+  },
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cell
+{
+  pub character:                        char,
+  pub flags:                            Style,
+  pub font:                             u8,
+  pub fgColour:                         Colour,
+  pub bgColour:                         Colour,
+}
+
+impl Cell
+{
+  #[allow(clippy::redundant_field_names)]
+  pub fn new
+  (
+    character:                          char,
+    flags:                              Style,
+    font:                               u8,
+    fgColour:                           Colour,
+    bgColour:                           Colour,
+  ) -> Self
+  {
+    Self
+    {
+      character:                        character,
+      flags:                            flags,
+      font:                             font,
+      fgColour:                         fgColour,
+      bgColour:                         bgColour,
+    }
+  }
+
+  pub fn plain
+  (
+    character:                          char,
+  ) -> Self
+  {
+    Self::new
+    (
+      character,
+      Style::None,
+      0,
+      Colour::Default,                  Colour::Default,
+    )
+  }
+}
+
+impl Default for Cell
+{
+  fn default
+  (
+  ) -> Self
+  {
+    Self::plain(' ')
+  }
+}
+
+//a grid of sizeX × sizeY cells, row by row, the frames are rendered into
+#[derive(Clone)]
+pub struct CellBuffer
+{
+  pub sizeX:                            usize,
+  pub sizeY:                            usize,
+  cells:                                Box<[Cell]>,
+}
+
+impl CellBuffer
+{
+  #[allow(clippy::redundant_field_names)]
+  pub fn new
+  (
+    sizeX:                              usize,
+    sizeY:                              usize,
+  ) -> Self
+  {
+    Self
+    {
+      sizeX:                            sizeX,
+      sizeY:                            sizeY,
+      cells:                            vec!( Cell::default(); sizeX * sizeY ).into_boxed_slice(),
+    }
+  }
+
+  pub fn resize
+  (
+    &mut self,
+    sizeX:                              usize,
+    sizeY:                              usize,
+  )
+  {
+    *self                               =                                       Self::new ( sizeX, sizeY );
+  }
+
+  pub fn clear
+  (
+    &mut self,
+  )
+  {
+    for cell                            in                                      self.cells.iter_mut()
+    {
+      *cell                             =                                       Cell::default();
+    }
+  }
+
+  pub fn cell
+  (
+    &self,
+    x:                                  usize,
+    y:                                  usize,
+  ) -> Option<&Cell>
+  {
+    if ( x < self.sizeX )
+    && ( y < self.sizeY )
+    {
+      Some(&self.cells [ x + y * self.sizeX ])
+    }
+    else
+    {
+      None
+    }
+  }
+
+  pub fn setCell
+  (
+    &mut self,
+    x:                                  usize,
+    y:                                  usize,
+    cell:                               Cell,
+  )
+  {
+    if ( x < self.sizeX )
+    && ( y < self.sizeY )
+    {
+      self.cells [ x + y * self.sizeX ] =                                       cell;
+    }
+  }
+
+  //the characters of row y without any styling
+  pub fn line
+  (
+    &self,
+    y:                                  usize,
+  ) -> String
+  {
+    ( 0 .. self.sizeX )
+      .filter_map ( | x | self.cell( x, y ) )
+      .map        ( | cell | cell.character )
+      .collect()
+  }
+
+  fn fill
+  (
+    &mut self,
+    cell:                               Cell,
+    minX:                               usize,
+    minY:                               usize,
+    maxX:                               usize,
+    maxY:                               usize,
+  )
+  {
+    for y                               in                                      minY .. maxY
+    {
+      for x                             in                                      minX .. maxX
+      {
+        self.setCell ( x, y, cell );
+      }
+    }
+  }
+
+  #[allow(unused_variables)]
+  #[allow(clippy::too_many_arguments)]
+  pub fn drawStatusFrame
+  (
+    &mut self,
+    this:                               &StatusFrame,
+    events:                             &EventSender,
+    lenX:                               usize,
+    lenY:                               usize,
+    minX:                               usize,
+    minY:                               usize,
+    maxX:                               usize,
+    maxY:                               usize,
+    cutX:                               usize,
+    cutY:                               usize,
+  )
+  {
+    self.fill ( Cell::plain ( this.bgChar ), minX, minY, minX + lenX, minY + 1 );
+    let mut offs:                 isize =                                       this.offs;
+    let mut shift:                usize =                                       0;
+    if offs < 0
+    {
+      shift                             =                                       -offs as usize;
+      offs                              =                                       0;
+    }
+    if shift < lenX
+    {
+      let offs:                   usize =                                       offs as usize + cutX;
+      for ( index, char )               in                                      this.text.chars().skip( offs ).take( lenX - shift ).enumerate()
+      {
+        if char == '\x1b'
+        {
+          break;
+        }
+        self.setCell ( minX + shift + index, minY, Cell::plain ( char ) );
+      }
+    }
+  }
+
+  #[allow(unused_variables)]
+  #[allow(clippy::too_many_arguments)]
+  pub fn drawTextFrame
+  (
+    &mut self,
+    this:                               &TextFrame,
+    events:                             &EventSender,
+    lenX:                               usize,
+    lenY:                               usize,
+    minX:                               usize,
+    minY:                               usize,
+    maxX:                               usize,
+    maxY:                               usize,
+    cutX:                               usize,
+    cutY:                               usize,
+  )
+  {
+    self.fill ( Cell::plain ( this.bgChar ), minX, minY, maxX, maxY );
+
+    let mut offsX:  isize               =                                       this.offsX;
+    let mut shiftX: usize               =                                       0;
+    if offsX < 0
+    {
+      shiftX                            =                                       -offsX as usize;
+      offsX                             =                                       0;
+    }
+    if shiftX < lenX
+    {
+      let offsX:      usize             =                                       offsX as usize + cutX;
+      let mut offsY:  isize             =                                       this.offsY;
+      let mut shiftY: usize             =                                       0;
+      if offsY < 0
+      {
+        shiftY                          =                                       -offsY as usize;
+        offsY                           =                                       0;
+      }
+      if shiftY < lenY
+      {
+        let offsY:                usize =                                       offsY as usize + cutY;
+        let posX:                 usize =                                       minX + shiftX;
+        let posY:                 usize =                                       minY + shiftY;
+        for ( indexY, line )            in                                      this.lines.iter().skip( offsY ).take( lenY - shiftY ).enumerate()
+        {
+          for ( indexX, char )          in                                      line.chars().skip( offsX ).take( lenX - shiftX ).enumerate()
+          {
+            if char == '\x1b'
+            {
+              break;
+            }
+            self.setCell ( posX + indexX, posY + indexY, Cell::plain ( char ) );
+          }
+        }
+      }
+    }
+  }
+
+  #[allow(unused_variables)]
+  #[allow(clippy::too_many_arguments)]
+  pub fn drawEditorFrame
+  (
+    &mut self,
+    this:                               &EditorFrame,
+    events:                             &EventSender,
+    lenX:                               usize,
+    lenY:                               usize,
+    minX:                               usize,
+    minY:                               usize,
+    maxX:                               usize,
+    maxY:                               usize,
+    cutX:                               usize,
+    cutY:                               usize,
+  )
+  {
+    self.fill ( Cell::plain ( this.bgChar ), minX, minY, maxX, maxY );
+
+    let mut offsX:                isize =                                       this.offsX;
+    let mut shiftX:               usize =                                       0;
+    if offsX < 0
+    {
+      shiftX                            =                                       -offsX as usize;
+      offsX                             =                                       0;
+    }
+    if shiftX < lenX
+    {
+      let offsX:                  usize =                                       offsX as usize + cutX;
+      let mut offsY:              isize =                                       this.offsY;
+      let mut shiftY:             usize =                                       0;
+      if offsY < 0
+      {
+        shiftY                          =                                       -offsY as usize;
+        offsY                           =                                       0;
+      }
+      if shiftY < lenY
+      {
+        let offsY:                usize =                                       offsY as usize + cutY;
+        let posX:                 usize =                                       minX + shiftX;
+        let posY:                 usize =                                       minY + shiftY;
+        for ( indexY, words )           in                                      this.lines.iter().skip( offsY ).take( lenY - shiftY ).enumerate()
+        {
+          //the horizontal offset applies to the whole line, not to every single word
+          let characters
+          = words
+              .iter()
+              .flat_map ( | word | word.word.chars().map ( move | char | ( char, word ) ) )
+              .skip     ( offsX )
+              .take     ( lenX - shiftX );
+          for ( indexX, ( char, word ) )
+                                        in                                      characters.enumerate()
+          {
+            if char == '\x1b'
+            {
+              break;
+            }
+            self.setCell
+            (
+              posX + indexX,            posY + indexY,
+              Cell::new
+              (
+                char,
+                word.flags,
+                word.font,
+                word.fgColour,          word.bgColour,
+              )
+            );
+          }
+        }
+      }
+    }
+  }
+
+  #[allow(unused_variables)]
+  #[allow(clippy::too_many_arguments)]
+  pub fn drawPixelFrame
+  (
+    &mut self,
+    this:                               &PixelFrame,
+    events:                             &EventSender,
+    lenX:                               usize,
+    lenY:                               usize,
+    minX:                               usize,
+    minY:                               usize,
+    maxX:                               usize,
+    maxY:                               usize,
+    cutX:                               usize,
+    cutY:                               usize,
+  )
+  {
+  }
+
+  #[allow(unused_variables)]
+  #[allow(clippy::too_many_arguments)]
+  pub fn drawPlotFrame
+  (
+    &mut self,
+    this:                               &PlotFrame,
+    events:                             &EventSender,
+    lenX:                               usize,
+    lenY:                               usize,
+    minX:                               usize,
+    minY:                               usize,
+    maxX:                               usize,
+    maxY:                               usize,
+    cutX:                               usize,
+    cutY:                               usize,
+  )
+  {
+  }
+}
@@ -0,0 +1,858 @@
+use crate::
+{
+  Flags,
+  display::
+  {
+    DisplayID,
+  },
+  event::
+  {
+    Event,
+    EventType,
+    KeyAction,
+    Modifier,
+    MouseButton,
+  },
+  frame::
+  {
+    FrameID,
+  },
+};
+
+const TTY_PASTE_END:               &str =                                       "\x1b[201~";
+const TTY_PASTE_LIMIT:             usize =                                      1 << 20;      // bytes of pasted text held back at most, longer pastes come in parts
+const TTY_MOUSE_MODIFIERS:         usize =                                      0b1_1100;     // shift, alt and ctrl bits of the button of a SGR mouse report
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Debug)]
+enum TTYPrefix
+{
+  None,
+  CSI,
+  Mouse,
+  Query,                                                                        // answers of the terminal, CSI ? …
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Debug)]
+enum TTYState
+{
+  ExpectByte,
+  Escape,
+  CSI,
+  SS3,
+  ParseArgument,
+  SubArgument,                                                                  // after a colon, only the first one of the last parameter is kept
+  UTF8
+  {
+    character:                          u32,                                    // bits decoded so far
+    remaining:                          u8,                                     // continuation bytes still missing
+    minimum:                            u32,                                    // anything below would have been shorter
+  },
+  Paste
+  {
+    content:                            Vec<u8>,
+  },
+}
+
+//turns the bytes sent by a terminal into events, without any display or channel attached.
+//  sequences may be split across calls, the rest is kept until the next bytes arrive.
+//  the events are not routed, so keys have no frame and mouse events only their position on the screen.
+pub struct InputParser
+{
+  pub display:                          DisplayID,                              // put into every event
+  state:                                TTYState,
+  mouseState:                           MouseButton,
+  listOfParameters:                     Vec<usize>,
+  currentParameter:                     usize,
+  parameterPrefix:                      TTYPrefix,
+}
+
+impl InputParser
+{
+  pub fn new
+  (
+  ) -> Self
+  {
+    Self
+    {
+      display:                          DisplayID::None,
+      state:                            TTYState::ExpectByte,
+      mouseState:                       MouseButton::None,
+      listOfParameters:                 vec!(),
+      currentParameter:                 0,
+      parameterPrefix:                  TTYPrefix::None,
+    }
+  }
+
+  //the events completed by these bytes.
+  pub fn parse
+  (
+    &mut self,
+    listOfBytes:                        &[u8],
+  ) -> Vec<Event>
+  {
+    let mut listOfEvents: Vec<Event>    =                                       vec!();
+    for &byte                           in                                      listOfBytes.iter()
+    {
+      self.state.nextState
+      (
+        byte,
+        self.display,
+        &mut listOfEvents,
+        &mut self.mouseState,
+        &mut self.listOfParameters,
+        &mut self.currentParameter,
+        &mut self.parameterPrefix,
+      );
+    }
+    listOfEvents
+  }
+
+  //whether the bytes so far might as well have been the escape key itself.
+  //  if nothing follows in time, timeout() tells which key it was.
+  pub fn isAmbiguous
+  (
+    &self,
+  ) -> bool
+  {
+    self.state.isAmbiguous()
+  }
+
+  //whether a paste has begun, but not ended yet. Its text is held back until the end, unless it gets too long.
+  //  pasted text may arrive slowly, so only after a long pause, timeout() delivers the text so far, in case its end got lost.
+  pub fn isPasting
+  (
+    &self,
+  ) -> bool
+  {
+    matches!( self.state, TTYState::Paste { .. } )
+  }
+
+  //the key, that was pressed, if no sequence follows the escape, or the text pasted so far.
+  pub fn timeout
+  (
+    &mut self,
+  ) -> Option<Event>
+  {
+    let mut listOfEvents: Vec<Event>    =                                       vec!();
+    self.state.timeout ( self.display, &mut listOfEvents, &self.mouseState );
+    listOfEvents.pop()
+  }
+}
+
+impl Default for InputParser
+{
+  fn default
+  (
+  ) -> Self
+  {
+    Self::new()
+  }
+}
+
+impl TTYState
+{
+  fn nextState
+  (
+    &mut self,
+    byte:                               u8,
+    display:                            DisplayID,
+    listOfEvents:                       &mut Vec<Event>,
+    mouseState:                         &mut MouseButton,
+    listOfParameters:                   &mut Vec<usize>,
+    currentParameter:                   &mut usize,
+    parameterPrefix:                    &mut TTYPrefix,
+  )
+  {
+    match self
+    {
+      TTYState::ExpectByte                                                      =>
+      {
+        match byte
+        {
+          0x1b                                                                  =>
+          {
+            *self                       =                                       TTYState::Escape;
+          },
+          byte @ 0x20..=0x7e                                                    =>
+          {
+            //println!("char: {}", byte as char);
+            Self::sendKey ( EventType::Character(byte as char), Modifier::None, display, listOfEvents, mouseState );
+          },
+          0x0d                                                                  =>
+          {
+            Self::sendKey ( EventType::Return,    Modifier::None, display, listOfEvents, mouseState );
+          },
+          0x09                                                                  =>
+          {
+            Self::sendKey ( EventType::Tab,       Modifier::None, display, listOfEvents, mouseState );
+          },
+          0x08 | 0x7f                                                           =>
+          {
+            Self::sendKey ( EventType::Backspace, Modifier::None, display, listOfEvents, mouseState );
+          },
+          //the first byte tells the number of continuation bytes, 0xc0, 0xc1 and above 0xf4 would be too long.
+          byte @ 0xc2 ..= 0xdf                                                  =>
+          {
+            *self                       =                                       TTYState::UTF8 { character: ( byte & 0x1f ) as u32, remaining: 1, minimum: 0x80    };
+          },
+          byte @ 0xe0 ..= 0xef                                                  =>
+          {
+            *self                       =                                       TTYState::UTF8 { character: ( byte & 0x0f ) as u32, remaining: 2, minimum: 0x800   };
+          },
+          byte @ 0xf0 ..= 0xf4                                                  =>
+          {
+            *self                       =                                       TTYState::UTF8 { character: ( byte & 0x07 ) as u32, remaining: 3, minimum: 0x10000 };
+          },
+          0x80 ..= 0xff                                                         =>
+          {
+            Self::sendKey ( EventType::Warning("invalid UTF-8 sequence in input"), Modifier::None, display, listOfEvents, mouseState );
+          },
+          //Ctrl-Space, Ctrl-A to Ctrl-Z, and Ctrl-\ to Ctrl-_.
+          byte @ 0x00 ..= 0x1f                                                  =>
+          {
+            let character: char
+            = match byte
+              {
+                0x00                    =>                                      ' ',
+                0x01 ..= 0x1a           =>                                      ( byte + 0x60 ) as char,
+                _                       =>                                      ( byte + 0x40 ) as char,
+              };
+            Self::sendKey ( EventType::Ctrl(character), Modifier::Ctrl, display, listOfEvents, mouseState );
+          },
+        }
+      },
+      TTYState::Escape                                                          =>
+      {
+        match byte
+        {
+          0x5b                                                                  =>
+          {
+            *self                       =                                       TTYState::CSI;
+          },
+          0x4f                                                                  =>
+          {
+            *self                       =                                       TTYState::SS3;
+          },
+          0x1b                                                                  =>
+          {
+            Self::sendKey ( EventType::Escape, Modifier::None, display, listOfEvents, mouseState );
+          },
+          character @ 0x20 ..= 0x7e                                             =>
+          {
+            *self                       =                                       TTYState::ExpectByte;
+            Self::sendKey ( EventType::Alt(character as char), Modifier::Alt, display, listOfEvents, mouseState );
+          },
+          _                                                                     =>
+          {
+            //not a sequence, so the escape key was pressed before something else.
+            Self::sendKey ( EventType::Escape, Modifier::None, display, listOfEvents, mouseState );
+            *self                       =                                       TTYState::ExpectByte;
+            self.nextState ( byte, display, listOfEvents, mouseState, listOfParameters, currentParameter, parameterPrefix );
+          },
+        }
+      },
+      TTYState::CSI                                                             =>
+      {
+        match byte
+        {
+          r @ 0x30 ..= 0x39                                                     =>
+          {
+            *parameterPrefix            =                                       TTYPrefix::CSI;
+            *listOfParameters           =                                       vec!();
+            *currentParameter           =                                       r as usize - 0x30;
+            *self                       =                                       TTYState::ParseArgument;
+          },
+          0x3c                                                                  =>
+          {
+            *parameterPrefix            =                                       TTYPrefix::Mouse;
+            *listOfParameters           =                                       vec!();
+            *currentParameter           =                                       0;
+            *self                       =                                       TTYState::ParseArgument;
+          },
+          0x3f                                                                  =>
+          {
+            *parameterPrefix            =                                       TTYPrefix::Query;
+            *listOfParameters           =                                       vec!();
+            *currentParameter           =                                       0;
+            *self                       =                                       TTYState::ParseArgument;
+          },
+          //the terminal window got or lost the focus.
+          focus @ ( 0x49 | 0x4f )                                               =>
+          {
+            *self                       =                                       TTYState::ExpectByte;
+            let focus: EventType
+            = match focus
+              {
+                0x49                    =>                                      EventType::DisplayFocusGained(display),
+                _                       =>                                      EventType::DisplayFocusLost(display),
+              };
+            let newEvent: Event
+            = Event::new
+              (
+                focus,
+                display,                FrameID::None,
+                0,                      0,
+                *mouseState,
+              );
+            listOfEvents.push ( newEvent );
+          },
+          last @ 0x40 ..= 0x7e                                                  =>
+          {
+            *self                       =                                       TTYState::ExpectByte;
+            if let Some(( key, modifiers )) = decodeKey ( last, &[] )
+            {
+              Self::sendKey ( key, modifiers, display, listOfEvents, mouseState );
+            }
+          },
+          _                                                                     =>
+          {
+            *self                       =                                       TTYState::ExpectByte;
+          }
+        }
+      },
+      TTYState::UTF8 { character, remaining, minimum }                          =>
+      {
+        if ( byte & 0xc0 ) == 0x80
+        {
+          let character: u32            =                                       ( *character << 6 ) | ( byte & 0x3f ) as u32;
+          if *remaining > 1
+          {
+            *self                       =                                       TTYState::UTF8 { character: character, remaining: *remaining - 1, minimum: *minimum };
+          }
+          else
+          {
+            //overlong encodings and surrogates are not valid characters either.
+            let valid: bool             =                                       character >= *minimum;
+            *self                       =                                       TTYState::ExpectByte;
+            match char::from_u32 ( character )
+            {
+              Some(character) if valid  =>                                      Self::sendKey ( EventType::Character(character),                     Modifier::None, display, listOfEvents, mouseState ),
+              _                         =>                                      Self::sendKey ( EventType::Warning("invalid UTF-8 sequence in input"), Modifier::None, display, listOfEvents, mouseState ),
+            }
+          }
+        }
+        else
+        {
+          //the sequence was cut short, but the byte itself might still be valid.
+          Self::sendKey ( EventType::Warning("invalid UTF-8 sequence in input"), Modifier::None, display, listOfEvents, mouseState );
+          *self                         =                                       TTYState::ExpectByte;
+          self.nextState ( byte, display, listOfEvents, mouseState, listOfParameters, currentParameter, parameterPrefix );
+        }
+      },
+      TTYState::Paste { content }                                               =>
+      {
+        content.push ( byte );
+        if content.ends_with ( TTY_PASTE_END.as_bytes() )
+        {
+          content.truncate ( content.len() - TTY_PASTE_END.len() );
+          let text: String              =                                       String::from_utf8_lossy ( content ).into_owned();
+          *self                         =                                       TTYState::ExpectByte;
+          Self::sendKey ( EventType::Paste(text), Modifier::None, display, listOfEvents, mouseState );
+        }
+        else if content.len() >= TTY_PASTE_LIMIT
+        {
+          let text: String              =                                       splitPaste ( content );
+          Self::sendKey ( EventType::Paste(text), Modifier::None, display, listOfEvents, mouseState );
+        }
+      },
+      TTYState::SS3                                                             =>
+      {
+        *self                           =                                       TTYState::ExpectByte;
+        if let Some(( key, modifiers )) = decodeKey ( byte, &[] )
+        {
+          Self::sendKey ( key, modifiers, display, listOfEvents, mouseState );
+        }
+      },
+      TTYState::SubArgument                                                     =>
+      {
+        match byte
+        {
+          r @ 0x30 ..= 0x39                                                     =>
+          {
+            *currentParameter           =                                       currentParameter.saturating_mul ( 10 ).saturating_add ( r as usize - 0x30 );
+          },
+          0x3b                                                                  =>
+          {
+            *currentParameter           =                                       0;
+            *self                       =                                       TTYState::ParseArgument;
+          },
+          last @ 0x40 ..= 0x7e
+                if *parameterPrefix == TTYPrefix::CSI                           =>
+          {
+            //the kitty keyboard protocol appends the event type to the modifiers, e.g. CSI 97;1:3u releases a.
+            let action: KeyAction
+            = match *currentParameter
+              {
+                2 if listOfParameters.len() == 2
+                                        =>                                      KeyAction::Repeat,
+                3 if listOfParameters.len() == 2
+                                        =>                                      KeyAction::Release,
+                _                       =>                                      KeyAction::Press,
+              };
+            *self                       =                                       TTYState::ExpectByte;
+            if let Some(( key, modifiers )) = decodeKey ( last, listOfParameters )
+            {
+              Self::sendAction ( key, modifiers, action, display, listOfEvents, mouseState );
+            }
+          },
+          _                                                                     =>
+          {
+            *self                       =                                       TTYState::ExpectByte;
+          },
+        }
+      },
+      TTYState::ParseArgument                                                   =>
+      {
+        match byte
+        {
+          r @ 0x30 ..= 0x39                                                     =>
+          {
+            *currentParameter           =                                       currentParameter.saturating_mul ( 10 ).saturating_add ( r as usize - 0x30 );
+          },
+          0x3b                                                                  =>
+          {
+            //println!("newArgument: {}", *currentParameter);
+            listOfParameters.push(*currentParameter);
+            *currentParameter           =                                       0;
+          },
+          0x3a                                                                  =>
+          {
+            listOfParameters.push(*currentParameter);
+            *currentParameter           =                                       0;
+            *self                       =                                       TTYState::SubArgument;
+          },
+          0x4d  if listOfParameters.len() == 2
+                && *parameterPrefix == TTYPrefix::Mouse                         =>
+          {
+            listOfParameters.push(*currentParameter);
+            let x                       =                                       listOfParameters[1].saturating_sub ( 1 );
+            let y                       =                                       listOfParameters[2].saturating_sub ( 1 );
+            let mut theEvent: Option<EventType>
+                                        =                                       None;
+            match listOfParameters[0] & !TTY_MOUSE_MODIFIERS
+            {
+              0                                                                 =>
+              {
+                *mouseState             |=                                      MouseButton::LeftDown;
+                theEvent                =                                       Some(EventType::MouseLeftButtonPressed);
+              },
+              1                                                                 =>
+              {
+                *mouseState             |=                                      MouseButton::MiddleDown;
+                theEvent                =                                       Some(EventType::MouseMiddleButtonPressed);
+              },
+              2                                                                 =>
+              {
+                *mouseState             |=                                      MouseButton::RightDown;
+                theEvent                =                                       Some(EventType::MouseRightButtonPressed);
+              },
+              32                                                                =>
+              {
+                theEvent                =                                       Some(EventType::MouseMoveWithLeftButton);
+              },
+              33                                                                =>
+              {
+                theEvent                =                                       Some(EventType::MouseMoveWithMiddleButton);
+              },
+              34                                                                =>
+              {
+                theEvent                =                                       Some(EventType::MouseMoveWithRightButton);
+              },
+              35                                                                =>
+              {
+                theEvent                =                                       Some(EventType::MouseOver);
+              }
+              64                                                                =>
+              {
+                theEvent                =                                       Some(EventType::MouseWheelUp);
+              },
+              65                                                                =>
+              {
+                theEvent                =                                       Some(EventType::MouseWheelDown);
+              },
+              66                                                                =>
+              {
+                theEvent                =                                       Some(EventType::MouseWheelLeft);
+              },
+              67                                                                =>
+              {
+                theEvent                =                                       Some(EventType::MouseWheelRight);
+              },
+              128                                                               =>
+              {
+                *mouseState             |=                                      MouseButton::BackDown;
+                theEvent                =                                       Some(EventType::MouseBackButtonPressed);
+              },
+              129                                                               =>
+              {
+                *mouseState             |=                                      MouseButton::ForwardDown;
+                theEvent                =                                       Some(EventType::MouseForwardButtonPressed);
+              },
+              160                                                               =>
+              {
+                theEvent                =                                       Some(EventType::MouseMoveWithBackButton);
+              },
+              161                                                               =>
+              {
+                theEvent                =                                       Some(EventType::MouseMoveWithForwardButton);
+              },
+              _                                                                 => { /* invalid */ },
+            }
+            *self                       =                                       TTYState::ExpectByte;
+            if let Some(theEvent) = theEvent
+            {
+              let mut newEvent: Event
+              = Event::new
+                (
+                  theEvent,
+                  display,              FrameID::None,
+                  x,                    y,
+                  *mouseState,
+                );
+              newEvent.modifiers        =                                       decodeMouseModifiers ( listOfParameters[0] );
+              listOfEvents.push ( newEvent );
+            }
+          },
+          0x6d  if listOfParameters.len() == 2
+                && *parameterPrefix == TTYPrefix::Mouse                         =>
+          {
+            listOfParameters.push(*currentParameter);
+            let x                       =                                       listOfParameters[1].saturating_sub ( 1 );
+            let y                       =                                       listOfParameters[2].saturating_sub ( 1 );
+            let mut theEvent: Option<EventType>
+                                        =                                       None;
+            match listOfParameters[0] & !TTY_MOUSE_MODIFIERS
+            {
+              0                                                                 =>
+              {
+                *mouseState             &=                                      !MouseButton::LeftDown;
+                theEvent                =                                       Some(EventType::MouseLeftButtonReleased);
+              },
+              1                                                                 =>
+              {
+                *mouseState             &=                                      !MouseButton::MiddleDown;
+                theEvent                =                                       Some(EventType::MouseMiddleButtonReleased);
+              },
+              2                                                                 =>
+              {
+                *mouseState             &=                                      !MouseButton::RightDown;
+                theEvent                =                                       Some(EventType::MouseRightButtonReleased);
+              },
+              128                                                               =>
+              {
+                *mouseState             &=                                      !MouseButton::BackDown;
+                theEvent                =                                       Some(EventType::MouseBackButtonReleased);
+              },
+              129                                                               =>
+              {
+                *mouseState             &=                                      !MouseButton::ForwardDown;
+                theEvent                =                                       Some(EventType::MouseForwardButtonReleased);
+              },
+              _                                                                 => { /* invalid */ },
+            }
+            *self                       =                                       TTYState::ExpectByte;
+            if let Some(theEvent) = theEvent
+            {
+              let mut newEvent: Event
+              = Event::new
+                (
+                  theEvent,
+                  display,              FrameID::None,
+                  x,                    y,
+                  *mouseState,
+                );
+              newEvent.modifiers        =                                       decodeMouseModifiers ( listOfParameters[0] );
+              listOfEvents.push ( newEvent );
+            }
+          },
+          0x52  if listOfParameters.len() == 1
+                && *parameterPrefix == TTYPrefix::CSI                           =>
+          {
+            listOfParameters.push(*currentParameter);
+            //println!("size: {}x{}", listOfParameters[1], listOfParameters[0]);
+            *self                       =                                        TTYState::ExpectByte;
+            let newEvent: Event
+            = Event::new
+              (
+                EventType::CursorPosition,
+                display,              FrameID::None,
+                listOfParameters[1],
+                listOfParameters[0],
+                *mouseState,
+              );
+            listOfEvents.push ( newEvent );
+          },
+          last @ 0x40 ..= 0x7e
+                if *parameterPrefix == TTYPrefix::Query                         =>
+          {
+            listOfParameters.push(*currentParameter);
+            *self                       =                                       TTYState::ExpectByte;
+            let answer: EventType
+            = match last
+              {
+                b'u'                    =>                                      EventType::KeyboardProtocol ( listOfParameters[0] as Flags ),
+                b'c'                    =>                                      EventType::DeviceAttributes,
+                _                       =>                                      return,
+              };
+            let newEvent: Event
+            = Event::new
+              (
+                answer,
+                display,              FrameID::None,
+                0,                    0,
+                *mouseState,
+              );
+            listOfEvents.push ( newEvent );
+          },
+          last @ 0x40 ..= 0x7e
+                if *parameterPrefix == TTYPrefix::CSI                           =>
+          {
+            listOfParameters.push(*currentParameter);
+            *self                       =                                       TTYState::ExpectByte;
+            if ( last == b'~' )
+            && ( *listOfParameters == [ 200 ] )
+            {
+              //everything up to CSI 201~ is pasted text, even if it looks like keys.
+              *self                     =                                       TTYState::Paste { content: vec!() };
+            }
+            else if let Some(( key, modifiers )) = decodeKey ( last, listOfParameters )
+            {
+              Self::sendKey ( key, modifiers, display, listOfEvents, mouseState );
+            }
+          },
+          _                                                                     =>
+          {
+           //println!("cannot parse {:?} {}", listOfParameters, r as char);
+           *self                        =                                       TTYState::ExpectByte;
+          },
+        }
+      },
+    }
+  }
+
+  //whether an escape sequence has begun, that might as well have been the escape key itself.
+  fn isAmbiguous
+  (
+    &self,
+  ) -> bool
+  {
+    matches!( self, TTYState::Escape | TTYState::CSI | TTYState::SS3 )
+  }
+
+  //nothing followed in time, so the escape key was pressed, maybe together with [ or O.
+  //  a paste stays a paste, but the text so far is not held back any longer, in case its end got lost.
+  fn timeout
+  (
+    &mut self,
+    display:                            DisplayID,
+    listOfEvents:                       &mut Vec<Event>,
+    mouseState:                         &MouseButton,
+  )
+  {
+    let ( key, modifiers )
+    = match self
+      {
+        TTYState::Escape                =>                                      ( EventType::Escape,   Modifier::None ),
+        TTYState::CSI                   =>                                      ( EventType::Alt('['), Modifier::Alt  ),
+        TTYState::SS3                   =>                                      ( EventType::Alt('O'), Modifier::Alt  ),
+        TTYState::Paste { content }     =>
+        {
+          let text: String              =                                       splitPaste ( content );
+          if !text.is_empty()
+          {
+            Self::sendKey ( EventType::Paste(text), Modifier::None, display, listOfEvents, mouseState );
+          }
+          return;
+        },
+        _                               =>                                      return,
+      };
+    *self                               =                                       TTYState::ExpectByte;
+    Self::sendKey ( key, modifiers, display, listOfEvents, mouseState );
+  }
+
+  fn sendKey
+  (
+    key:                                EventType,
+    modifiers:                          Modifier,
+    display:                            DisplayID,
+    listOfEvents:                       &mut Vec<Event>,
+    mouseState:                         &MouseButton,
+  )
+  {
+    Self::sendAction ( key, modifiers, KeyAction::Press, display, listOfEvents, mouseState );
+  }
+
+  //the frame is left to whoever routes the event.
+  fn sendAction
+  (
+    key:                                EventType,
+    modifiers:                          Modifier,
+    action:                             KeyAction,
+    display:                            DisplayID,
+    listOfEvents:                       &mut Vec<Event>,
+    mouseState:                         &MouseButton,
+  )
+  {
+    let mut newEvent: Event
+    = Event::new
+      (
+        key,
+        display,                        FrameID::None,
+        0,                              0,
+        *mouseState,
+      );
+    newEvent.modifiers                  =                                       modifiers;
+    newEvent.action                     =                                       action;
+    listOfEvents.push ( newEvent );
+  }
+}
+
+//the key of the final byte of a CSI or SS3 sequence, e.g. CSI 1;5A is Ctrl-Up and CSI 15~ is F5.
+//  CSI 1;mR would be F3 with modifiers, but this cannot be told apart from a cursor position.
+fn decodeKey
+(
+  last:                                 u8,
+  listOfParameters:                     &[usize],
+) -> Option<( EventType, Modifier )>
+{
+  let modifiers: Modifier
+  = match listOfParameters.get ( 1 )
+    {
+      Some(&modifiers) if modifiers > 0 =>                                      Modifier::from_bits_truncate ( ( modifiers - 1 ) as Flags ),
+      _                                 =>                                      Modifier::None,
+    };
+  let key: EventType
+  = match last
+    {
+      b'A'                              =>                                      EventType::Up,
+      b'B'                              =>                                      EventType::Down,
+      b'C'                              =>                                      EventType::Right,
+      b'D'                              =>                                      EventType::Left,
+      b'H'                              =>                                      EventType::Home,
+      b'F'                              =>                                      EventType::End,
+      b'P'                              =>                                      EventType::Function(1),
+      b'Q'                              =>                                      EventType::Function(2),
+      b'R'                              =>                                      EventType::Function(3),
+      b'S'                              =>                                      EventType::Function(4),
+      b'Z'                              =>                                      return Some(( EventType::Tab, modifiers | Modifier::Shift )),
+      b'u'                              =>                                      return decodeCodepoint ( *listOfParameters.first()?, modifiers ),
+      b'~'                              =>
+      {
+        match *listOfParameters.first()?
+        {
+          1 | 7                         =>                                      EventType::Home,
+          2                             =>                                      EventType::Insert,
+          3                             =>                                      EventType::Delete,
+          4 | 8                         =>                                      EventType::End,
+          5                             =>                                      EventType::PageUp,
+          6                             =>                                      EventType::PageDown,
+          //the numbers of function keys have gaps.
+          number @ 11 ..= 15            =>                                      EventType::Function( ( number - 10 ) as u8 ),
+          number @ 17 ..= 21            =>                                      EventType::Function( ( number - 11 ) as u8 ),
+          number @ 23 ..= 26            =>                                      EventType::Function( ( number - 12 ) as u8 ),
+          number @ 28 ..= 29            =>                                      EventType::Function( ( number - 13 ) as u8 ),
+          number @ 31 ..= 34            =>                                      EventType::Function( ( number - 14 ) as u8 ),
+          _                             =>                                      return None,
+        }
+      },
+      _                                 =>                                      return None,
+    };
+  Some(( key, modifiers ))
+}
+
+//the button of a SGR mouse report carries shift in bit 2, alt in bit 3 and ctrl in bit 4.
+fn decodeMouseModifiers
+(
+  button:                               usize,
+) -> Modifier
+{
+  let mut modifiers: Modifier           =                                       Modifier::None;
+  if ( button & 0b0_0100 ) != 0                                                 { modifiers |= Modifier::Shift }
+  if ( button & 0b0_1000 ) != 0                                                 { modifiers |= Modifier::Alt   }
+  if ( button & 0b1_0000 ) != 0                                                 { modifiers |= Modifier::Ctrl  }
+  modifiers
+}
+
+//the key of CSI code;modifiers u as sent by the kitty keyboard protocol, so Ctrl-I is not Tab anymore.
+//  keys without a character of their own use codes of the private use area.
+fn decodeCodepoint
+(
+  code:                                 usize,
+  modifiers:                            Modifier,
+) -> Option<( EventType, Modifier )>
+{
+  let key: EventType
+  = match code
+    {
+      27                                =>                                      EventType::Escape,
+      13 | 57414                        =>                                      EventType::Return,
+      9                                 =>                                      EventType::Tab,
+      8 | 127                           =>                                      EventType::Backspace,
+      57362                             =>                                      EventType::Pause,
+      number @ 57376 ..= 57398          =>                                      EventType::Function( ( number - 57363 ) as u8 ),
+      number @ 57399 ..= 57408          =>                                      EventType::Character( ( b'0' + ( number - 57399 ) as u8 ) as char ),
+      number @ 57409 ..= 57413          =>                                      EventType::Character( "./*-+".as_bytes() [ number - 57409 ] as char ),
+      57415                             =>                                      EventType::Character('='),
+      //lock, media, modifier and the remaining keypad keys.
+      57344 ..= 63743                   =>                                      return None,
+      code                              =>
+      {
+        let character: char             =                                       char::from_u32 ( code as u32 )?;
+        if ( modifiers & Modifier::Ctrl ) != Modifier::None
+        {
+          EventType::Ctrl(character)
+        }
+        else if ( modifiers & Modifier::Alt ) != Modifier::None
+        {
+          EventType::Alt(character)
+        }
+        else if ( modifiers & Modifier::Shift ) != Modifier::None
+        {
+          EventType::Character( character.to_uppercase().next().unwrap_or ( character ) )
+        }
+        else
+        {
+          EventType::Character(character)
+        }
+      },
+    };
+  Some(( key, modifiers ))
+}
+
+//takes the pasted text out of the content, but keeps the bytes, that might begin the end of the paste or a character.
+fn splitPaste
+(
+  content:                              &mut Vec<u8>,
+) -> String
+{
+  let mut split: usize                  =                                       content.len();
+  for length                            in                                      ( 1 .. TTY_PASTE_END.len() ).rev()
+  {
+    if content.ends_with ( &TTY_PASTE_END.as_bytes() [ .. length ] )
+    {
+      split                             -=                                      length;
+      break;
+    }
+  }
+  if let Some(start) = content [ .. split ].iter().rposition ( | &byte | ( byte & 0xc0 ) != 0x80 )
+  {
+    let length: usize
+    = match content [ start ]
+      {
+        0xc0 ..= 0xdf                   =>                                      2,
+        0xe0 ..= 0xef                   =>                                      3,
+        0xf0 ..= 0xf7                   =>                                      4,
+        _                               =>                                      1,
+      };
+    if split - start < length
+    {
+      split                             =                                       start;
+    }
+  }
+  let rest: Vec<u8>                     =                                       content.split_off ( split );
+  let text: String                      =                                       String::from_utf8_lossy ( content ).into_owned();
+  *content                              =                                       rest;
+  text
+}
@@ -0,0 +1,238 @@
+use crate::
+{
+  display::
+  {
+    Display,
+    DisplayFlag,
+    DisplayID,
+    DisplayType,
+    cell::
+    {
+      Cell,
+      CellBuffer,
+    },
+  },
+  event::
+  {
+    EventSender,
+  },
+  frame::
+  {
+    EditorFrame,
+    FrameID,
+    PixelFrame,
+    PlotFrame,
+    StatusFrame,
+    TextFrame,
+  },
+};
+
+use std::
+{
+  sync::
+  {
+    Arc,
+    Mutex,
+  },
+  time::
+  {
+    Duration,
+    SystemTime
+  },
+};
+
+//headless display, that renders into a grid of cells instead of a terminal, e.g. for tests.
+pub struct MemoryDisplay
+{
+  pub buffer:                           CellBuffer,
+  pub title:                            String,
+  pub active:                           bool,
+}
+
+impl MemoryDisplay
+{
+  //builds the display itself, like the constructors of the other backends.
+  #[allow(clippy::new_ret_no_self)]
+  #[allow(clippy::redundant_field_names)]
+  pub fn new
+  (
+    flags:                              DisplayFlag,
+    offsX:                              isize,
+    offsY:                              isize,
+    sizeX:                              usize,
+    sizeY:                              usize,
+    refreshRate:                        u64,
+  ) -> Display
+  {
+    Display
+    {
+      flags:                            flags | DisplayFlag::NeedRefresh | DisplayFlag::NeedRemap,
+      this:                             DisplayID::None,
+      offsX:                            offsX,
+      offsY:                            offsY,
+      sizeX:                            sizeX,
+      sizeY:                            sizeY,
+      cursorX:                          0,
+      cursorY:                          0,
+      mapOfFrames:                      Arc::new(Mutex::new(None)),
+      mainFrame:                        FrameID::None,
+      focusedFrame:                     Arc::new(Mutex::new(Box::new(FrameID::None))),
+      reportedFocus:                    FrameID::None,
+      lastRefresh:                      SystemTime::now(),
+      nextRefresh:                      Duration::from_nanos(refreshRate),
+      changedFrames:                    vec!(),
+      display:
+      DisplayType::Memory
+      (
+        MemoryDisplay
+        {
+          buffer:                       CellBuffer::new ( sizeX, sizeY ),
+          title:                        String::new(),
+          active:                       false,
+        }
+      )
+    }
+  }
+
+  pub fn cell
+  (
+    &self,
+    x:                                  usize,
+    y:                                  usize,
+  ) -> Option<&Cell>
+  {
+    self.buffer.cell ( x, y )
+  }
+
+  pub fn line
+  (
+    &self,
+    y:                                  usize,
+  ) -> String
+  {
+    self.buffer.line ( y )
+  }
+
+  pub fn changeTitle
+  (
+    &mut self,
+    title:                              String,
+  )
+  {
+    self.title                          =                                       title;
+  }
+
+  pub fn turnOn
+  (
+    &mut self,
+    title:                              String,
+  )
+  {
+    self.title                          =                                       title;
+    self.active                         =                                       true;
+    self.buffer.clear();
+  }
+
+  pub fn turnOff
+  (
+    &mut self,
+  )
+  {
+    self.active                         =                                       false;
+  }
+
+  #[allow(clippy::too_many_arguments)]
+  pub fn drawStatusFrame
+  (
+    &mut self,
+    this:                               &StatusFrame,
+    events:                             &EventSender,
+    lenX:                               usize,
+    lenY:                               usize,
+    minX:                               usize,
+    minY:                               usize,
+    maxX:                               usize,
+    maxY:                               usize,
+    cutX:                               usize,
+    cutY:                               usize,
+  )
+  {
+    self.buffer.drawStatusFrame ( this, events, lenX, lenY, minX, minY, maxX, maxY, cutX, cutY );
+  }
+
+  #[allow(clippy::too_many_arguments)]
+  pub fn drawTextFrame
+  (
+    &mut self,
+    this:                               &TextFrame,
+    events:                             &EventSender,
+    lenX:                               usize,
+    lenY:                               usize,
+    minX:                               usize,
+    minY:                               usize,
+    maxX:                               usize,
+    maxY:                               usize,
+    cutX:                               usize,
+    cutY:                               usize,
+  )
+  {
+    self.buffer.drawTextFrame   ( this, events, lenX, lenY, minX, minY, maxX, maxY, cutX, cutY );
+  }
+
+  #[allow(clippy::too_many_arguments)]
+  pub fn drawEditorFrame
+  (
+    &mut self,
+    this:                               &EditorFrame,
+    events:                             &EventSender,
+    lenX:                               usize,
+    lenY:                               usize,
+    minX:                               usize,
+    minY:                               usize,
+    maxX:                               usize,
+    maxY:                               usize,
+    cutX:                               usize,
+    cutY:                               usize,
+  )
+  {
+    self.buffer.drawEditorFrame ( this, events, lenX, lenY, minX, minY, maxX, maxY, cutX, cutY );
+  }
+
+  #[allow(clippy::too_many_arguments)]
+  pub fn drawPixelFrame
+  (
+    &mut self,
+    this:                               &PixelFrame,
+    events:                             &EventSender,
+    lenX:                               usize,
+    lenY:                               usize,
+    minX:                               usize,
+    minY:                               usize,
+    maxX:                               usize,
+    maxY:                               usize,
+    cutX:                               usize,
+    cutY:                               usize,
+  )
+  {
+    self.buffer.drawPixelFrame  ( this, events, lenX, lenY, minX, minY, maxX, maxY, cutX, cutY );
+  }
+
+  #[allow(clippy::too_many_arguments)]
+  pub fn drawPlotFrame
+  (
+    &mut self,
+    this:                               &PlotFrame,
+    events:                             &EventSender,
+    lenX:                               usize,
+    lenY:                               usize,
+    minX:                               usize,
+    minY:                               usize,
+    maxX:                               usize,
+    maxY:                               usize,
+    cutX:                               usize,
+    cutY:                               usize,
+  )
+  {
+    self.buffer.drawPlotFrame   ( this, events, lenX, lenY, minX, minY, maxX, maxY, cutX, cutY );
+  }
+}
@@ -1,12 +1,32 @@
+pub mod cell;
+pub mod input;
+pub mod memory;
+#[cfg(feature = "display-tty")]
+mod signal;
 #[cfg(feature = "display-tty")]
 pub mod tty;
 
 use crate::
 {
   Flags,
+  uid::
+  {
+    Slots,
+    UID,
+  },
+  display::
+  {
+    memory::
+    {
+      MemoryDisplay,
+    },
+  },
   event::
   {
+    Event,
     EventSender,
+    EventType,
+    MouseButton,
   },
   frame::
   {
@@ -61,10 +81,11 @@ pub trait WriteableFd:  Write + AsRawFd                                       {}
 impl <Type:             Read  + AsRawFd + Send  + Sync> ReadableFd  for Type  {}
 impl <Type:             Write + AsRawFd               > WriteableFd for Type  {}
 
-pub type DisplayID                      =                                       usize;
+pub type DisplayID                      =                                       UID;
 
 pub enum DisplayType
 {
+  Memory(MemoryDisplay),
   #[cfg(feature = "display-tty")]
   TTY(TTYDisplay),
 }
@@ -75,13 +96,141 @@ bitflags!
   {
     const None                          =                                       0b0000_0000_0000_0000_0000_0000_0000_0000;
     const RealTime                      =                                       0b0000_0000_0000_0000_0000_0000_0000_0001;
+    const ResetTerminal                 =                                       0b0000_0000_0000_0000_0000_0000_0000_0010;
+    const RestoreOnPanic                =                                       0b0000_0000_0000_0000_0000_0000_0000_0100;
+    const RestoreOnTermination          =                                       0b0000_0000_0000_0000_0000_0000_0000_1000;
+    const SuspendOnCtrlZ                =                                       0b0000_0000_0000_0000_0000_0000_0001_0000;
+    const KittyKeyboard                 =                                       0b0000_0000_0000_0000_0000_0000_0010_0000;
     //…
+    const TurnedOn                      =                                       0b0010_0000_0000_0000_0000_0000_0000_0000;
     const NeedRefresh                   =                                       0b0100_0000_0000_0000_0000_0000_0000_0000;
     const NeedRemap                     =                                       0b1000_0000_0000_0000_0000_0000_0000_0000;
     const MaskRefresh                   =                                       DisplayFlag::NeedRefresh.bits | DisplayFlag::NeedRemap.bits;
   }
 }
 
+//a frame drawn on the display, so mouse events can be routed to it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MappedFrame
+{
+  pub frame:                            FrameID,
+  pub posX:                             isize,                                  // top left corner of the frame on the display, before it was cut
+  pub posY:                             isize,
+  pub parent:                           Option<usize>,                          // index of the parent frame in the map
+}
+
+impl MappedFrame
+{
+  pub const None:                       MappedFrame =                           MappedFrame { frame: FrameID::None, posX: 0, posY: 0, parent: None };
+
+  pub fn new
+  (
+    frame:                              FrameID,
+    posX:                               isize,
+    posY:                               isize,
+    parent:                             Option<usize>,
+  ) -> Self
+  {
+    Self
+    {
+      frame:                            frame,
+      posX:                             posX,
+      posY:                             posY,
+      parent:                           parent,
+    }
+  }
+}
+
+//the topmost frame of every cell of the display, which is the last one drawn there.
+pub struct FrameMap
+{
+  pub sizeX:                            usize,
+  pub sizeY:                            usize,
+  listOfCells:                          Box<[Option<usize>]>,                   // index into the list of frames
+  listOfFrames:                         Vec<MappedFrame>,                       // every frame drawn, parents before their children
+}
+
+impl FrameMap
+{
+  pub fn new
+  (
+    sizeX:                              usize,
+    sizeY:                              usize,
+  ) -> Self
+  {
+    Self
+    {
+      sizeX:                            sizeX,
+      sizeY:                            sizeY,
+      listOfCells:                      vec!( None; sizeX * sizeY ).into_boxed_slice(),
+      listOfFrames:                     vec!(),
+    }
+  }
+
+  //the frame covers the cells from min to max, until a frame drawn later covers them again.
+  //  returns its index, so its subframes can refer to it as their parent.
+  pub fn insert
+  (
+    &mut self,
+    frame:                              MappedFrame,
+    minX:                               usize,
+    minY:                               usize,
+    maxX:                               usize,
+    maxY:                               usize,
+  ) -> usize
+  {
+    let index: usize                    =                                       self.listOfFrames.len();
+    self.listOfFrames.push ( frame );
+    for y                               in                                      minY .. maxY.min ( self.sizeY )
+    {
+      for x                             in                                      minX .. maxX.min ( self.sizeX )
+      {
+        self.listOfCells [ x + y * self.sizeX ]
+                                        =                                       Some(index);
+      }
+    }
+    index
+  }
+
+  //the topmost frame at the cell, if any.
+  //  the map might still have the old size, while the terminal was resized.
+  pub fn at
+  (
+    &self,
+    x:                                  usize,
+    y:                                  usize,
+  ) -> Option<&MappedFrame>
+  {
+    if ( x < self.sizeX )
+    && ( y < self.sizeY )
+    {
+      self.listOfCells [ x + y * self.sizeX ].map ( | index | &self.listOfFrames [ index ] )
+    }
+    else
+    {
+      None
+    }
+  }
+
+  //the topmost frame at the cell followed by all its ancestors up to the main frame of the display.
+  pub fn path
+  (
+    &self,
+    x:                                  usize,
+    y:                                  usize,
+  ) -> Vec<FrameID>
+  {
+    let mut listOfFrames: Vec<FrameID>  =                                       vec!();
+    let mut next: Option<&MappedFrame>  =                                       self.at ( x, y );
+    while let Some(frame) = next
+    {
+      listOfFrames.push ( frame.frame );
+      next                              =                                       frame.parent.map ( | index | &self.listOfFrames [ index ] );
+    }
+    listOfFrames
+  }
+}
+
 pub struct Display
 {
   pub flags:                            DisplayFlag,
@@ -92,34 +241,92 @@ pub struct Display
   pub sizeY:                            usize,
   pub cursorX:                          usize,
   pub cursorY:                          usize,
-  mapOfFrames:                          Arc<Mutex<Option<Box<[FrameID]>>>>,
+  mapOfFrames:                          Arc<Mutex<Option<FrameMap>>>,
   pub mainFrame:                        FrameID,
   pub focusedFrame:                     Arc<Mutex<Box<FrameID>>>,
+  reportedFocus:                        FrameID,                                // focused frame, as last reported by focus events
   pub lastRefresh:                      SystemTime,
   pub nextRefresh:                      Duration,
+  pub changedFrames:                    Vec<FrameID>,                           // frames changed since this display was drawn last
   pub display:                          DisplayType,
 }
 
 impl Display
 {
+  //draws the frame and its subframes, but skips all subtrees without changes since the last draw, unless forced to.
+  //  returns whether anything was drawn.
   pub fn draw
   (
     &mut self,
-    listOfFrames:                       &mut Vec<Option<Frame>>,
+    listOfFrames:                       &mut Slots<Frame>,
     events:                             &EventSender,
     drawFrame:                          FrameID,
     posX:                               isize,
     posY:                               isize,
     lenX:                               usize,
     lenY:                               usize,
-  )
+    force:                              bool,
+  ) -> bool
+  {
+    //every frame is visited anyway, so the map of frames is built on the way.
+    let mut map: Option<FrameMap>
+    = if ( self.flags & DisplayFlag::NeedRemap ) != DisplayFlag::None
+      {
+        Some(FrameMap::new ( self.sizeX, self.sizeY ))
+      }
+      else
+      {
+        None
+      };
+    let drawn: bool                     =                                       self.drawTree ( listOfFrames, events, drawFrame, posX, posY, lenX, lenY, force, &mut map, None );
+    self.changedFrames.clear();
+    if let Some(map) = map
+    {
+      if let Ok(mut mapOfFrames) = self.mapOfFrames.lock()
+      {
+        *mapOfFrames                    =                                       Some(map);
+        self.flags                      &=                                      !DisplayFlag::NeedRemap;
+      }
+    }
+    drawn
+  }
+
+  //the frames at the cell of the display, from the topmost one down to the main frame.
+  pub fn framesAt
+  (
+    &self,
+    x:                                  usize,
+    y:                                  usize,
+  ) -> Vec<FrameID>
   {
+    match self.mapOfFrames.lock()
+    {
+      Ok(mapOfFrames)                   =>                                      mapOfFrames.as_ref().map ( | map | map.path ( x, y ) ).unwrap_or_default(),
+      Err(_)                            =>                                      vec!(),
+    }
+  }
+
+  fn drawTree
+  (
+    &mut self,
+    listOfFrames:                       &mut Slots<Frame>,
+    events:                             &EventSender,
+    drawFrame:                          FrameID,
+    posX:                               isize,
+    posY:                               isize,
+    lenX:                               usize,
+    lenY:                               usize,
+    force:                              bool,
+    map:                                &mut Option<FrameMap>,
+    parent:                             Option<usize>,
+  ) -> bool
+  {
+    let mut drawn:                 bool =                                       false;
     let mut minX:                 isize =                                       posX;
     let mut minY:                 isize =                                       posY;
     let mut maxX:                 isize =                                       posX + lenX as isize;
     let mut maxY:                 isize =                                       posY + lenY as isize;
-    if ( drawFrame == 0 )
-    || ( drawFrame > listOfFrames.len() )
+    if listOfFrames.slot ( drawFrame ).is_none()
     {
       // invalid frame to draw
     }
@@ -128,9 +335,8 @@ impl Display
     {
       // ignore
     }
-    else if listOfFrames [ drawFrame - 1 ].is_some()
+    else if let Some(mut refFrame) = listOfFrames.slot ( drawFrame ).and_then ( Option::take )
     {
-      let mut refFrame                  =                                       listOfFrames [ drawFrame - 1 ].take();
       let mut cutX:               usize =                                       0;
       let mut cutY:               usize =                                       0;
       if minX < 0
@@ -155,76 +361,63 @@ impl Display
       let minY:                   usize =                                       minY as usize;
       let maxX:                   usize =                                       maxX as usize;
       let maxY:                   usize =                                       maxY as usize;
-      let lenX:                   usize =                                       ( maxX - minX )  as usize;
-      let lenY:                   usize =                                       ( maxY - minY )  as usize;
-      if ( self.flags & DisplayFlag::NeedRemap ) != DisplayFlag::None           //do I have to remap the map of frames?
-      {
-        if let Ok(mut mapOfFrames) = self.mapOfFrames.lock()                    //can I access it?
-        {
-          //allocate new map of frames
-          let mut theMapOfFrames: Vec<FrameID>
-                                        =                                       Vec::with_capacity( ( self.sizeX * self.sizeY ) as usize );
-          theMapOfFrames.resize(( self.sizeX * self.sizeY ) as usize, 0);
-          let mut theMapOfFrames: Box<[FrameID]>
-                                        =                                       theMapOfFrames.into_boxed_slice();
-
-          //remap
-          for y                         in                                      minY .. maxY
-          {
-            for x                       in                                      minX .. maxX
-            {
-              theMapOfFrames [ x + y * self.sizeX ]
-                                        =                                       drawFrame;
-            }
-          }
-
-          //do not have to remap, because I did
-          self.flags                    &=                                      !DisplayFlag::NeedRemap;
-
-          //replace old map of frames
-          *mapOfFrames                  =                                       Some(theMapOfFrames);
-        }
-      }
-      match refFrame.as_mut().unwrap()
+      let lenX:                   usize =                                       maxX - minX;
+      let lenY:                   usize =                                       maxY - minY;
+      //subframes are drawn later, so they cover their parent in the map as on the display.
+      let mapped: Option<usize>         =                                       map.as_mut().map ( | map | map.insert ( MappedFrame::new ( drawFrame, posX, posY, parent ), minX, minY, maxX, maxY ) );
+      let force:                   bool =                                       force || self.changedFrames.contains ( &drawFrame );
+      let skip:                    bool =                                       !force && refFrame.isLeaf();
+      match refFrame
       {
+        _ if skip                       =>                                      {},
         Frame::Status ( ref frame )     =>
         {
+          drawn                         =                                       true;
           match self.display
           {
+            DisplayType::Memory(ref mut output)  => output.drawStatusFrame ( frame, events, lenX, lenY, minX, minY, maxX, maxY, cutX, cutY ),
             #[cfg(feature = "display-tty")]
-            DisplayType::TTY(ref mut output)  => output.drawStatusFrame ( frame, events, lenX, lenY, minX, minY, maxY, maxY, cutX, cutY ),
+            DisplayType::TTY(ref mut output)     => output.drawStatusFrame ( frame, events, lenX, lenY, minX, minY, maxX, maxY, cutX, cutY ),
           }
         },
         Frame::Text ( ref frame )       =>
         {
+          drawn                         =                                       true;
           match self.display
           {
+            DisplayType::Memory(ref mut output)  => output.drawTextFrame   ( frame, events, lenX, lenY, minX, minY, maxX, maxY, cutX, cutY ),
             #[cfg(feature = "display-tty")]
-            DisplayType::TTY(ref mut output)  => output.drawTextFrame   ( frame, events, lenX, lenY, minX, minY, maxY, maxY, cutX, cutY ),
+            DisplayType::TTY(ref mut output)     => output.drawTextFrame   ( frame, events, lenX, lenY, minX, minY, maxX, maxY, cutX, cutY ),
           }
         },
         Frame::Editor ( ref frame )     =>
         {
+          drawn                         =                                       true;
           match self.display
           {
+            DisplayType::Memory(ref mut output)  => output.drawEditorFrame ( frame, events, lenX, lenY, minX, minY, maxX, maxY, cutX, cutY ),
             #[cfg(feature = "display-tty")]
-            DisplayType::TTY(ref mut output)  => output.drawEditorFrame ( frame, events, lenX, lenY, minX, minY, maxY, maxY, cutX, cutY ),
+            DisplayType::TTY(ref mut output)     => output.drawEditorFrame ( frame, events, lenX, lenY, minX, minY, maxX, maxY, cutX, cutY ),
           }
         },
         Frame::Pixel( ref frame )       =>
         {
+          drawn                         =                                       true;
           match self.display
           {
+            DisplayType::Memory(ref mut output)  => output.drawPixelFrame  ( frame, events, lenX, lenY, minX, minY, maxX, maxY, cutX, cutY ),
             #[cfg(feature = "display-tty")]
-            DisplayType::TTY(ref mut output)  => output.drawPixelFrame  ( frame, events, lenX, lenY, minX, minY, maxY, maxY, cutX, cutY ),
+            DisplayType::TTY(ref mut output)     => output.drawPixelFrame  ( frame, events, lenX, lenY, minX, minY, maxX, maxY, cutX, cutY ),
           }
         },
         Frame::Plot( ref frame )        =>
         {
+          drawn                         =                                       true;
           match self.display
           {
+            DisplayType::Memory(ref mut output)  => output.drawPlotFrame   ( frame, events, lenX, lenY, minX, minY, maxX, maxY, cutX, cutY ),
             #[cfg(feature = "display-tty")]
-            DisplayType::TTY(ref mut output)  => output.drawPlotFrame   ( frame, events, lenX, lenY, minX, minY, maxY, maxY, cutX, cutY ),
+            DisplayType::TTY(ref mut output)     => output.drawPlotFrame   ( frame, events, lenX, lenY, minX, minY, maxX, maxY, cutX, cutY ),
           }
         },
         Frame::Parent ( ref mut frame ) =>
@@ -236,7 +429,7 @@ impl Display
             {
               let countX                =                                       frame.gridBordersX.len() - 1;
               let countY                =                                       frame.gridBordersY.len() - 1;
-              for mut instance          in                                      &mut frame.listOfInstances
+              for instance              in                                      &mut frame.listOfInstances
               {
                 if ( instance.gridOriginX <= countX )
                 && ( instance.gridOriginY <= countY )
@@ -261,6 +454,9 @@ impl Display
               }
             }
           }
+          //instances without tiling might overlap, so everything drawn after a changed one has to be drawn again.
+          let overlapping:         bool =                                       matches!( frame.typeOfTiling, Tiling::None );
+          let mut force:           bool =                                       force;
           for instance                  in                                      &mut frame.listOfInstances
           {
             let posX                    =                                       posX + instance.posX;
@@ -268,39 +464,164 @@ impl Display
             let lenX                    =                                       instance.lenX;
             let lenY                    =                                       instance.lenY;
             let next                    =                                       instance.frame;
-            self.draw
-            (
-              listOfFrames,
-              events,
-              next,
-              posX,                     posY,
-              lenX,                     lenY,
-            );
+            let changed
+            = self.drawTree
+              (
+                listOfFrames,
+                events,
+                next,
+                posX,                   posY,
+                lenX,                   lenY,
+                force,
+                map,
+                mapped,
+              );
+            force                       |=                                      changed && overlapping;
+            drawn                       |=                                      changed;
           }
         },
         Frame::Layers( ref frame ) =>
         {
+          //every layer above a changed one has to be drawn again.
+          let mut force:           bool =                                       force;
           for layer                     in                                      &frame.listOfLayers
           {
-            self.draw
-            (
-              listOfFrames,
-              events,
-              *layer,
-              posX,                     posY,
-              lenX,                     lenY,
-            );
+            force
+            |= self.drawTree
+              (
+                listOfFrames,
+                events,
+                *layer,
+                posX,                   posY,
+                lenX,                   lenY,
+                force,
+                map,
+                mapped,
+              );
           }
+          drawn                         |=                                      force;
         },
       }
-      listOfFrames [ drawFrame - 1 ]    =                                       refFrame.take();
+      if let Some(slot) = listOfFrames.slot ( drawFrame )
+      {
+        *slot                           =                                       Some(refFrame);
+      }
     }
     else
     {
       //wut
     }
+    drawn
+  }
+
+  //returns false, if the lock shared with the listener is poisoned.
+  pub fn focusFrame
+  (
+    &mut self,
+    events:                             &EventSender,
+    frame:                              FrameID,
+  ) -> bool
+  {
+    match self.focusedFrame.lock()
+    {
+      Ok(mut focus)                     =>                                      **focus = frame,
+      Err(_)                            =>                                      return false,
+    }
+    self.reportFocus ( events );
+    true
   }
 
+  //applies whatever the listener noticed in the meantime, e.g. a new size of the terminal,
+  //  and reports, if the focus was moved to another frame.
+  pub fn update
+  (
+    &mut self,
+    events:                             &EventSender,
+  )
+  {
+    self.reportFocus ( events );
+    match self.display
+    {
+      DisplayType::Memory(_)               => {},
+      #[cfg(feature = "display-tty")]
+      DisplayType::TTY(ref mut output)     =>
+      {
+        if output.takeResume()
+        {
+          output.resume ( events, self.this );
+          self.flags                    |=                                      DisplayFlag::MaskRefresh;
+        }
+        if let Some(( sizeX, sizeY )) = output.takeResize()
+        {
+          self.resize ( events, sizeX, sizeY );
+        }
+      },
+    }
+  }
+
+  //sends FrameFocusLost and FrameFocusGained, if the focused frame is not the one reported last.
+  fn reportFocus
+  (
+    &mut self,
+    events:                             &EventSender,
+  )
+  {
+    let focus: FrameID                  =                                       self.focusedFrame.lock().map ( | focus | **focus ).unwrap_or ( self.reportedFocus );
+    if focus != self.reportedFocus
+    {
+      for ( event, frame )              in                                      [ ( EventType::FrameFocusLost, self.reportedFocus ), ( EventType::FrameFocusGained, focus ) ]
+      {
+        if !frame.isNone()
+        {
+          events.send ( Event::new ( event, self.this, frame, 0, 0, MouseButton::None ) ).unwrap();
+        }
+      }
+      self.reportedFocus                =                                       focus;
+    }
+  }
+
+  //everything has to be remapped and drawn again after this.
+  #[allow(unused_variables)]
+  pub fn resize
+  (
+    &mut self,
+    events:                             &EventSender,
+    sizeX:                              usize,
+    sizeY:                              usize,
+  )
+  {
+    self.sizeX                          =                                       sizeX;
+    self.sizeY                          =                                       sizeY;
+    if let Ok(mut mapOfFrames) = self.mapOfFrames.lock()
+    {
+      *mapOfFrames                      =                                       None;
+    }
+    self.flags                          |=                                      DisplayFlag::MaskRefresh;
+    match self.display
+    {
+      DisplayType::Memory(ref mut output)  => output.buffer.resize ( sizeX, sizeY ),
+      #[cfg(feature = "display-tty")]
+      DisplayType::TTY(ref mut output)     => output.resize      ( events, self.this, sizeX, sizeY ),
+    }
+  }
+
+  //only terminals have to tell the escape key from escape sequences.
+  #[allow(unused_variables)]
+  pub fn setEscapeTimeout
+  (
+    &mut self,
+    timeout:                            Duration,
+  )
+  {
+    match self.display
+    {
+      DisplayType::Memory(_)               => {},
+      #[cfg(feature = "display-tty")]
+      DisplayType::TTY(ref mut output)     => output.setEscapeTimeout ( timeout ),
+    }
+  }
+
+  #[allow(unused_variables)]
   pub fn changeTitle
   (
     &mut self,
@@ -310,11 +631,13 @@ impl Display
   {
     match self.display
     {
+      DisplayType::Memory(ref mut output)  => output.changeTitle ( title ),
       #[cfg(feature = "display-tty")]
-      DisplayType::TTY(ref mut output)  => output.changeTitle ( events, self.this, title ),
+      DisplayType::TTY(ref mut output)     => output.changeTitle ( events, self.this, title ),
     }
   }
 
+  #[allow(unused_variables)]
   pub fn turnOn
   (
     &mut self,
@@ -322,13 +645,20 @@ impl Display
     title:                              String,
   )
   {
+    //the main frame is focused by turning on, which is not worth an event.
+    if let Ok(focus) = self.focusedFrame.lock()
+    {
+      self.reportedFocus                =                                       **focus;
+    }
     match self.display
     {
+      DisplayType::Memory(ref mut output)  => output.turnOn      ( title ),
       #[cfg(feature = "display-tty")]
-      DisplayType::TTY(ref mut output)  => output.turnOn      ( events, self.this, title, self.sizeX, self.sizeY, self.mapOfFrames.clone(), self.focusedFrame.clone() ),
+      DisplayType::TTY(ref mut output)     => output.turnOn      ( events, self.this, self.flags, title, self.sizeX, self.sizeY, self.mapOfFrames.clone(), self.focusedFrame.clone() ),
     }
   }
 
+  #[allow(unused_variables)]
   pub fn turnOff
   (
     &mut self,
@@ -337,8 +667,9 @@ impl Display
   {
     match self.display
     {
+      DisplayType::Memory(ref mut output)  => output.turnOff     (),
       #[cfg(feature = "display-tty")]
-      DisplayType::TTY(ref mut output)  => output.turnOff     ( &events, self.this ),
+      DisplayType::TTY(ref mut output)     => output.turnOff     ( &events, self.this ),
     }
   }
 }
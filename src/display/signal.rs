@@ -0,0 +1,522 @@
+use std::
+{
+  cell::
+  {
+    UnsafeCell,
+  },
+  mem::
+  {
+    MaybeUninit,
+  },
+  os::
+  {
+    unix::
+    {
+      io::
+      {
+        RawFd,
+      },
+    },
+  },
+  sync::
+  {
+    Once,
+    atomic::
+    {
+      AtomicI32,
+      AtomicU8,
+      Ordering,
+    },
+  },
+  panic,
+  ptr,
+};
+
+//bytes written into the wake-up pipe of a listener.
+pub const TTY_WAKEUP_QUIT:              u8 =                                    b'q';
+pub const TTY_WAKEUP_RESIZE:            u8 =                                    b'r';
+pub const TTY_WAKEUP_SUSPEND:           u8 =                                    b's';
+pub const TTY_WAKEUP_CONTINUE:          u8 =                                    b'c';
+
+//sequences sent to the terminal, when it is turned off.
+pub const TTY_RESET_TERMINAL:           &str =                                  "\x1bc";
+pub const TTY_RESTORE_SCREEN:           &str =                                  "\x1b[?1004l\x1b[?2004l\x1b[?1006l\x1b[?1003l\x1b[?25h\x1b[?1049l";
+pub const TTY_POP_KEYBOARD:             &str =                                  "\x1b[<u";
+
+const MAX_WAKEUPS:                      usize =                                 32;
+const MAX_TERMINALS:                    usize =                                 32;
+
+const SLOT_FREE:                        u8 =                                    0;
+const SLOT_BUSY:                        u8 =                                    1;    // being written or restored
+const SLOT_READY:                       u8 =                                    2;
+const SLOT_SUSPENDED:                   u8 =                                    3;    // restored, but will be turned on again on SIGCONT
+const SLOT_PANICKED:                    u8 =                                    4;    // restored, but will be turned on again, if the panic was caught
+
+//write ends of the wake-up pipes of all listeners.
+//  this is plain atomics, because the signal handler must neither lock nor allocate.
+static LIST_OF_WAKEUPS:                 [ AtomicI32; MAX_WAKEUPS ] =            [ const { AtomicI32::new ( -1 ) }; MAX_WAKEUPS ];
+static INSTALL_HANDLERS:                Once =                                  Once::new();
+static INSTALL_PANIC_HOOK:              Once =                                  Once::new();
+static INSTALL_TERMINATION:             Once =                                  Once::new();
+
+//everything needed to restore a terminal in raw mode, copied, so it can be used without the display.
+#[derive(Clone, Copy)]
+pub struct Terminal
+{
+  pub input:                            RawFd,
+  pub output:                           RawFd,
+  pub termios:                          libc::termios,
+  pub fcntl:                            i32,
+  pub reset:                            bool,                                   // whether to reset the terminal instead of restoring the screen
+  pub kitty:                            bool,                                   // whether the kitty keyboard protocol has to be turned off
+}
+
+struct TerminalSlot
+{
+  state:                                AtomicU8,
+  terminal:                             UnsafeCell<MaybeUninit<Terminal>>,
+}
+
+//the terminal is only accessed by whoever changed the state to busy.
+unsafe impl Sync for TerminalSlot {}
+
+static LIST_OF_TERMINALS:               [ TerminalSlot; MAX_TERMINALS ]
+                                        =                                       [ const { TerminalSlot { state: AtomicU8::new ( SLOT_FREE ), terminal: UnsafeCell::new ( MaybeUninit::uninit() ) } }; MAX_TERMINALS ];
+
+//handler of a signal before ours was installed.
+struct PreviousAction
+{
+  action:                               UnsafeCell<MaybeUninit<libc::sigaction>>,
+}
+
+//only written once, before the handler reading it is installed.
+unsafe impl Sync for PreviousAction {}
+
+static LIST_OF_TERMINATIONS:            [ PreviousAction; 2 ]
+                                        =                                       [ const { PreviousAction { action: UnsafeCell::new ( MaybeUninit::uninit() ) } }; 2 ];
+static LIST_OF_HANDLED:                 [ PreviousAction; 3 ]
+                                        =                                       [ const { PreviousAction { action: UnsafeCell::new ( MaybeUninit::uninit() ) } }; 3 ];
+
+//the pipe will be woken up on every signal handled here, returns false if there are too many pipes already.
+pub fn registerWakeup
+(
+  fd:                                   RawFd,
+) -> bool
+{
+  INSTALL_HANDLERS.call_once ( installHandlers );
+  LIST_OF_WAKEUPS
+    .iter()
+    .any ( | slot | slot.compare_exchange ( -1, fd, Ordering::SeqCst, Ordering::SeqCst ).is_ok() )
+}
+
+pub fn unregisterWakeup
+(
+  fd:                                   RawFd,
+)
+{
+  for slot                              in                                      LIST_OF_WAKEUPS.iter()
+  {
+    let _                               =                                       slot.compare_exchange ( fd, -1, Ordering::SeqCst, Ordering::SeqCst );
+  }
+}
+
+//a full pipe already wakes the listener up, so a failed write can be ignored.
+pub fn writePipe
+(
+  fd:                                   RawFd,
+  message:                              u8,
+)
+{
+  unsafe
+  {
+    libc::write ( fd, &message as *const u8 as *const libc::c_void, 1 );
+  }
+}
+
+fn installHandlers
+(
+)
+{
+  for &signal                           in                                      [ libc::SIGWINCH, libc::SIGTSTP, libc::SIGCONT ].iter()
+  {
+    handleSignal ( signal, unsafe { ( *previousAction ( signal ).action.get() ).as_mut_ptr() } );
+  }
+}
+
+//the previous handler is kept, unless it is null.
+fn handleSignal
+(
+  signal:                               libc::c_int,
+  previous:                             *mut libc::sigaction,
+)
+{
+  unsafe
+  {
+    let mut action: libc::sigaction     =                                       std::mem::zeroed();
+    action.sa_sigaction                 =                                       onSignal as extern "C" fn ( libc::c_int, *mut libc::siginfo_t, *mut libc::c_void ) as libc::sighandler_t;
+    action.sa_flags                     =                                       libc::SA_RESTART | libc::SA_SIGINFO;
+    libc::sigemptyset ( &mut action.sa_mask );
+    libc::sigaction ( signal, &action, previous );
+  }
+}
+
+fn previousAction
+(
+  signal:                               libc::c_int,
+) -> &'static PreviousAction
+{
+  match signal
+  {
+    libc::SIGWINCH                      =>                                      &LIST_OF_HANDLED [ 0 ],
+    libc::SIGTSTP                       =>                                      &LIST_OF_HANDLED [ 1 ],
+    _                                   =>                                      &LIST_OF_HANDLED [ 2 ],
+  }
+}
+
+//calls the handler, that was installed before ours, unless it is the default or ignores the signal.
+fn chainSignal
+(
+  signal:                               libc::c_int,
+  info:                                 *mut libc::siginfo_t,
+  context:                              *mut libc::c_void,
+)
+{
+  unsafe
+  {
+    let previous: &libc::sigaction      =                                       ( *previousAction ( signal ).action.get() ).assume_init_ref();
+    if ( previous.sa_sigaction == libc::SIG_DFL )
+    || ( previous.sa_sigaction == libc::SIG_IGN )
+    {
+      return;
+    }
+    if ( previous.sa_flags & libc::SA_SIGINFO ) != 0
+    {
+      let handler: extern "C" fn ( libc::c_int, *mut libc::siginfo_t, *mut libc::c_void )
+                                        =                                       std::mem::transmute ( previous.sa_sigaction );
+      handler ( signal, info, context );
+    }
+    else
+    {
+      let handler: extern "C" fn ( libc::c_int )
+                                        =                                       std::mem::transmute ( previous.sa_sigaction );
+      handler ( signal );
+    }
+  }
+}
+
+fn wakeAll
+(
+  message:                              u8,
+)
+{
+  for slot                              in                                      LIST_OF_WAKEUPS.iter()
+  {
+    let fd                              =                                       slot.load ( Ordering::SeqCst );
+    if fd >= 0
+    {
+      writePipe ( fd, message );
+    }
+  }
+}
+
+//errno of the calling thread, which every libc names differently.
+#[cfg(any(target_os = "linux", target_os = "emscripten", target_os = "fuchsia", target_os = "redox", target_os = "hurd", target_os = "dragonfly"))]
+fn errnoLocation
+(
+) -> *mut libc::c_int
+{
+  unsafe { libc::__errno_location() }
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios", target_os = "freebsd"))]
+fn errnoLocation
+(
+) -> *mut libc::c_int
+{
+  unsafe { libc::__error() }
+}
+
+#[cfg(any(target_os = "android", target_os = "netbsd", target_os = "openbsd"))]
+fn errnoLocation
+(
+) -> *mut libc::c_int
+{
+  unsafe { libc::__errno() }
+}
+
+#[cfg(any(target_os = "solaris", target_os = "illumos"))]
+fn errnoLocation
+(
+) -> *mut libc::c_int
+{
+  unsafe { libc::___errno() }
+}
+
+//whatever the interrupted code was about to read from errno is left untouched.
+extern "C" fn onSignal
+(
+  signal:                               libc::c_int,
+  info:                                 *mut libc::siginfo_t,
+  context:                              *mut libc::c_void,
+)
+{
+  let errno: libc::c_int                =                                       unsafe { *errnoLocation() };
+  match signal
+  {
+    libc::SIGWINCH                      =>
+    {
+      wakeAll ( TTY_WAKEUP_RESIZE );
+      chainSignal ( signal, info, context );
+    },
+    libc::SIGTSTP                       =>
+    {
+      let previous: *const libc::sigaction
+                                        =                                       unsafe { ( *previousAction ( signal ).action.get() ).as_ptr() };
+      //a process ignoring the signal is not stopped, so its terminals stay as they are.
+      if unsafe { ( *previous ).sa_sigaction } != libc::SIG_IGN
+      {
+        suspendTerminals();
+        wakeAll ( TTY_WAKEUP_SUSPEND );
+        //the signal is blocked until this handler returns, then the previous handler, by default stopping the process, gets it.
+        unsafe
+        {
+          libc::sigaction ( libc::SIGTSTP, previous, ptr::null_mut() );
+          libc::raise ( libc::SIGTSTP );
+        }
+      }
+    },
+    libc::SIGCONT                       =>
+    {
+      handleSignal ( libc::SIGTSTP, ptr::null_mut() );
+      resumeTerminals();
+      wakeAll ( TTY_WAKEUP_CONTINUE );
+      chainSignal ( signal, info, context );
+    },
+    _                                   =>                                      {},
+  }
+  unsafe
+  {
+    *errnoLocation()                    =                                       errno;
+  }
+}
+
+//the terminal will be restored by the panic hook and the termination handlers, until it is unregistered again.
+//  returns the index to unregister it, or None if there are too many terminals already.
+pub fn registerTerminal
+(
+  terminal:                             Terminal,
+) -> Option<usize>
+{
+  for ( index, slot )                   in                                      LIST_OF_TERMINALS.iter().enumerate()
+  {
+    if slot.state.compare_exchange ( SLOT_FREE, SLOT_BUSY, Ordering::SeqCst, Ordering::SeqCst ).is_ok()
+    {
+      unsafe
+      {
+        ( *slot.terminal.get() ).write ( terminal );
+      }
+      slot.state.store ( SLOT_READY, Ordering::SeqCst );
+      return Some(index);
+    }
+  }
+  None
+}
+
+//returns false, if the terminal has been restored already.
+pub fn unregisterTerminal
+(
+  index:                                usize,
+) -> bool
+{
+  let state                             =                                       &LIST_OF_TERMINALS [ index ].state;
+  if state.compare_exchange ( SLOT_READY, SLOT_FREE, Ordering::SeqCst, Ordering::SeqCst ).is_ok()
+  {
+    true
+  }
+  else
+  {
+    let _                               =                                       state.compare_exchange ( SLOT_SUSPENDED, SLOT_FREE, Ordering::SeqCst, Ordering::SeqCst );
+    let _                               =                                       state.compare_exchange ( SLOT_PANICKED,  SLOT_FREE, Ordering::SeqCst, Ordering::SeqCst );
+    false
+  }
+}
+
+//enters raw mode again after a caught panic, returns false, if the terminal was not restored by a panic.
+pub fn recoverTerminal
+(
+  index:                                usize,
+) -> bool
+{
+  let slot                              =                                       &LIST_OF_TERMINALS [ index ];
+  if slot.state.compare_exchange ( SLOT_PANICKED, SLOT_BUSY, Ordering::SeqCst, Ordering::SeqCst ).is_err()
+  {
+    return false;
+  }
+  enterRawMode ( unsafe { ( *slot.terminal.get() ).assume_init_ref() } );
+  slot.state.store ( SLOT_READY, Ordering::SeqCst );
+  true
+}
+
+//passes every terminal in the state from to the action and leaves it in the state to.
+//  this only uses async-signal-safe functions, so it can be called from a signal handler.
+fn changeTerminals
+(
+  from:                                 u8,
+  to:                                   u8,
+  action:                               fn ( &Terminal ),
+)
+{
+  for slot                              in                                      LIST_OF_TERMINALS.iter()
+  {
+    if slot.state.compare_exchange ( from, SLOT_BUSY, Ordering::SeqCst, Ordering::SeqCst ).is_ok()
+    {
+      action ( unsafe { ( *slot.terminal.get() ).assume_init_ref() } );
+      slot.state.store ( to, Ordering::SeqCst );
+    }
+  }
+}
+
+fn restoreTerminal
+(
+  terminal:                             &Terminal,
+)
+{
+  let sequence: &str
+  = if terminal.reset
+    {
+      TTY_RESET_TERMINAL
+    }
+    else
+    {
+      TTY_RESTORE_SCREEN
+    };
+  unsafe
+  {
+    if terminal.kitty
+    {
+      libc::write   ( terminal.output, TTY_POP_KEYBOARD.as_ptr() as *const libc::c_void, TTY_POP_KEYBOARD.len() );
+    }
+    libc::write     ( terminal.output, sequence.as_ptr() as *const libc::c_void, sequence.len() );
+    libc::tcsetattr ( terminal.output, libc::TCSANOW, &terminal.termios );
+    libc::fcntl     ( terminal.input,  libc::F_SETFL, terminal.fcntl );
+  }
+}
+
+//raw mode only, the screen is set up again by the display itself.
+fn enterRawMode
+(
+  terminal:                             &Terminal,
+)
+{
+  let mut termios                       =                                       terminal.termios;
+  unsafe
+  {
+    libc::cfmakeraw ( &mut termios );
+    libc::tcsetattr ( terminal.output, libc::TCSANOW, &termios );
+    libc::fcntl     ( terminal.input,  libc::F_SETFL, terminal.fcntl | libc::O_NONBLOCK );
+  }
+}
+
+//restores and unregisters all registered terminals.
+pub fn restoreTerminals
+(
+)
+{
+  changeTerminals ( SLOT_READY,     SLOT_FREE,      restoreTerminal );
+}
+
+fn suspendTerminals
+(
+)
+{
+  changeTerminals ( SLOT_READY,     SLOT_SUSPENDED, restoreTerminal );
+}
+
+fn resumeTerminals
+(
+)
+{
+  changeTerminals ( SLOT_SUSPENDED, SLOT_READY,     enterRawMode );
+}
+
+//stops the process group like the shell would on Ctrl-Z, the terminals are restored until it is continued.
+pub fn suspend
+(
+)
+{
+  unsafe
+  {
+    libc::kill ( 0, libc::SIGTSTP );
+  }
+}
+
+//restores all terminals before the message of a panic is printed.
+//  the panic might still be caught, so they stay registered, until they are recovered or turned off.
+pub fn restoreOnPanic
+(
+)
+{
+  INSTALL_PANIC_HOOK.call_once
+  (
+    ||
+    {
+      let previous                      =                                       panic::take_hook();
+      panic::set_hook
+      (
+        Box::new
+        (
+          move | info |
+          {
+            changeTerminals ( SLOT_READY, SLOT_PANICKED, restoreTerminal );
+            previous ( info );
+          }
+        )
+      );
+    }
+  );
+}
+
+//restores all terminals on SIGINT and SIGTERM, before the previous handler, by default termination, takes over.
+pub fn restoreOnTermination
+(
+)
+{
+  INSTALL_TERMINATION.call_once
+  (
+    ||
+    {
+      unsafe
+      {
+        let mut action: libc::sigaction =                                       std::mem::zeroed();
+        action.sa_sigaction             =                                       onTermination as extern "C" fn ( libc::c_int ) as libc::sighandler_t;
+        libc::sigemptyset ( &mut action.sa_mask );
+        for ( index, &signal )          in                                      [ libc::SIGINT, libc::SIGTERM ].iter().enumerate()
+        {
+          libc::sigaction ( signal, &action, ( *LIST_OF_TERMINATIONS [ index ].action.get() ).as_mut_ptr() );
+        }
+      }
+    }
+  );
+}
+
+extern "C" fn onTermination
+(
+  signal:                               libc::c_int,
+)
+{
+  let errno: libc::c_int                =                                       unsafe { *errnoLocation() };
+  restoreTerminals();
+  let index: usize
+  = match signal
+    {
+      libc::SIGINT                      =>                                      0,
+      _                                 =>                                      1,
+    };
+  //the signal is blocked until this handler returns, so the previous handler gets it afterwards.
+  unsafe
+  {
+    libc::sigaction ( signal, ( *LIST_OF_TERMINATIONS [ index ].action.get() ).as_ptr(), ptr::null_mut() );
+    libc::raise ( signal );
+    *errnoLocation()                    =                                       errno;
+  }
+}
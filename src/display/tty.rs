@@ -1,19 +1,45 @@
 use crate::
 {
+  Error,
+  Flags,
   display::
   {
     Display,
     DisplayFlag,
     DisplayID,
     DisplayType,
+    FrameMap,
+    MappedFrame,
     ReadableFd,
     WriteableFd,
+    input::
+    {
+      InputParser,
+    },
+    signal::
+    {
+      self,
+      Terminal,
+      TTY_POP_KEYBOARD,
+      TTY_RESET_TERMINAL,
+      TTY_RESTORE_SCREEN,
+      TTY_WAKEUP_CONTINUE,
+      TTY_WAKEUP_QUIT,
+      TTY_WAKEUP_RESIZE,
+      TTY_WAKEUP_SUSPEND,
+    },
+    cell::
+    {
+      Cell,
+      CellBuffer,
+    },
   },
   event::
   {
     Event,
     EventSender,
     EventType,
+    KeyAction,
     MouseButton,
   },
   frame::
@@ -36,20 +62,30 @@ use std::
 {
   io::
   {
+    self,
     Read,
     Write,
   },
   mem,
+  os::
+  {
+    unix::
+    {
+      io::
+      {
+        RawFd,
+      },
+    },
+  },
   sync::
   {
     Arc,
-    mpsc::
+    Mutex,
+    atomic::
     {
-      channel,
-      Receiver,
-      Sender,
+      AtomicBool,
+      Ordering,
     },
-    Mutex,
   },
   thread::
   {
@@ -59,43 +95,39 @@ use std::
   time::
   {
     Duration,
+    Instant,
     SystemTime
   },
 };
 
 const TTY_ESC:                     &str =                                       "\x1b";
 const TTY_CSI:                     &str =                                       "\x1b[";
-
-#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Debug)]
-pub enum TTYPrefix
-{
-  None,
-  Escape,
-  CSI,
-  Mouse,
-}
-
-#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Debug)]
-enum TTYState
-{
-  ExpectByte,
-  Escape,
-  CSI,
-  ParseArgument,
-}
+const TTY_ESCAPE_TIMEOUT:          u64 =                                        50;           // milliseconds
+const TTY_PASTE_TIMEOUT:           u64 =                                        2000;         // milliseconds without pasted bytes, before the end of a paste is considered lost
+const TTY_KITTY_FLAGS:             Flags =                                      0b11;         // disambiguate escape codes and report event types
+const TTY_KITTY_TIMEOUT:           u64 =                                        200;          // milliseconds to wait for the terminal to answer
 
 pub struct TTYDisplay
 {
   input:                                Arc<Mutex<Box<ReadableFd>>>,
   output:                               Box<WriteableFd>,
   listener:                             Option<JoinHandle<()>>,
-  messages:                             Option<Sender<bool>>,
+  wakeup:                               Option<( RawFd, RawFd )>,               // ( read end, write end ) of the pipe polled by the listener
+  resized:                              Arc<Mutex<Option<( usize, usize )>>>,   // new size reported by the listener, but not applied yet
+  resumed:                              Arc<AtomicBool>,                        // continued after being stopped, but the screen is not set up again yet
+  escapeTimeout:                        Arc<Mutex<Duration>>,                   // how long to wait for the rest of a sequence after ESC
   termios:                              libc::termios,
   fcntl:                                i32,
+  flags:                                DisplayFlag,                            // as turned on
+  kitty:                                bool,                                   // whether the terminal speaks the kitty keyboard protocol
+  registered:                           Option<usize>,                          // index for the panic hook and signal handlers
+  front:                                CellBuffer,
+  back:                                 CellBuffer,
 }
 
 impl TTYDisplay
 {
+  #[allow(clippy::new_ret_no_self)]
   pub fn new
   (
     flags:                              DisplayFlag,
@@ -106,14 +138,14 @@ impl TTYDisplay
     mut input:                          Box<ReadableFd>,
     mut output:                         Box<WriteableFd>,
     refreshRate:                        u64,
-  ) -> Result<Display, &'static str>
+  ) -> Result<Display, Error>
   {
     unsafe
     {
       let mut termios: libc::termios    =                                       mem::zeroed();
       if libc::tcgetattr( output.as_raw_fd(), &mut termios) < 0
       {
-        Err("cannot get termios structure")
+        Err(Error::lastOSError(DisplayID::None, "cannot get termios structure"))
       }
       else
       {
@@ -124,7 +156,7 @@ impl TTYDisplay
           Display
           {
             flags:                            flags | DisplayFlag::NeedRefresh | DisplayFlag::NeedRemap,
-            this:                             0,
+            this:                             DisplayID::None,
             offsX:                            offsX,
             offsY:                            offsY,
             sizeX:                            sizeX as usize,
@@ -132,10 +164,12 @@ impl TTYDisplay
             cursorX:                          cursorX,
             cursorY:                          cursorY,
             mapOfFrames:                      Arc::new(Mutex::new(None)),
-            mainFrame:                        0,
-            focusedFrame:                     Arc::new(Mutex::new(Box::new(0))),
+            mainFrame:                        FrameID::None,
+            focusedFrame:                     Arc::new(Mutex::new(Box::new(FrameID::None))),
+            reportedFocus:                    FrameID::None,
             lastRefresh:                      SystemTime::now(),
             nextRefresh:                      Duration::from_nanos(refreshRate),
+            changedFrames:                    vec!(),
             display:
             DisplayType::TTY
             (
@@ -144,9 +178,17 @@ impl TTYDisplay
                 input:                        Arc::new(Mutex::new(input)),
                 output:                       output,
                 listener:                     None,
-                messages:                     None,
+                wakeup:                       None,
+                resized:                      Arc::new(Mutex::new(None)),
+                resumed:                      Arc::new(AtomicBool::new(false)),
+                escapeTimeout:                Arc::new(Mutex::new(Duration::from_millis(TTY_ESCAPE_TIMEOUT))),
                 termios:                      termios,
                 fcntl:                        0,
+                flags:                        DisplayFlag::None,
+                kitty:                        false,
+                registered:                   None,
+                front:                        CellBuffer::new ( sizeX as usize, sizeY as usize ),
+                back:                         CellBuffer::new ( sizeX as usize, sizeY as usize ),
               }
             )
           }
@@ -160,7 +202,7 @@ impl TTYDisplay
     input:                              &mut Box<ReadableFd>,
     output:                             &mut Box<WriteableFd>,
     termios:                            &mut libc::termios,
-  ) -> Result<( u16, u16, u16, u16 ), &'static str>
+  ) -> Result<( u16, u16, u16, u16 ), Error>
   {
     unsafe
     {
@@ -178,20 +220,15 @@ impl TTYDisplay
           "{}999;999H{}6n\n",
           TTY_CSI,
           TTY_CSI,
-        ).unwrap();
-        let mut state: TTYState         =                                       TTYState::ExpectByte;
-        let mut mouseState              =                                       MouseButton::None;
-        let mut listOfParameters        =                                       vec!();
-        let mut currentParameter        =                                       0;
+        ).map_err ( | error | Error::IO ( DisplayID::None, "cannot send to tty", error ) )?;
+        let mut parser: InputParser     =                                       InputParser::new();
         let mut returnValue: Option<Event>
                                         =                                       None;
-        let mut parameterPrefix: TTYPrefix
-                                        =                                       TTYPrefix::None;
         let mut temp                    =                                       termios.clone();
         libc::cfmakeraw(&mut temp);
         if libc::tcsetattr( output.as_raw_fd(), libc::TCSAFLUSH, &mut temp) < 0
         {
-          Err("cannot enter raw mode")
+          Err(Error::lastOSError(DisplayID::None, "cannot enter raw mode"))
         }
         else
         {
@@ -201,27 +238,14 @@ impl TTYDisplay
             {
               if let Ok(byte) = byte
               {
-                returnValue
-                = state.nextState
-                (
-                  byte,
-                  None,
-                  0,
-                  None,
-                  None,
-                  0,                    0,
-                  &mut mouseState,
-                  &mut listOfParameters,
-                  &mut currentParameter,
-                  &mut parameterPrefix,
-                );
+                returnValue             =                                       parser.parse ( &[ byte ] ).pop();
               }
             }
           }
           let mut temp                  =                                       termios.clone();
           if libc::tcsetattr( output.as_raw_fd(), libc::TCSAFLUSH, &mut temp) < 0
           {
-            Err("cannot enter cooked mode")
+            Err(Error::lastOSError(DisplayID::None, "cannot enter cooked mode"))
           }
           else
           {
@@ -240,7 +264,7 @@ impl TTYDisplay
             }
             else
             {
-              Err("cannot determine terminal dimensions")
+              Err(Error::TerminalSize(DisplayID::None))
             }
           }
         }
@@ -275,28 +299,28 @@ impl TTYDisplay
         "{}]0;{}\x07",
         TTY_ESC,                        title,
       );
-    if !error.is_ok()
+    if let Err(error) = error
     {
       events.send
       (
         Event::new
         (
-          EventType::Error("cannot send to tty"),
-          display,                      0,
+          EventType::Error(Error::IO(display, "cannot send to tty", error)),
+          display,                      FrameID::None,
           0,                            0,
           MouseButton::None,
         )
       ).unwrap();
     }
     let error                           =                                       self.output.flush();
-    if !error.is_ok()
+    if let Err(error) = error
     {
       events.send
       (
         Event::new
         (
-          EventType::Error("cannot flush to tty"),
-          display,                      0,
+          EventType::Error(Error::IO(display, "cannot flush to tty", error)),
+          display,                      FrameID::None,
           0,                            0,
           MouseButton::None,
         )
@@ -309,13 +333,23 @@ impl TTYDisplay
     &mut self,
     events:                             EventSender,
     display:                            DisplayID,
+    flags:                              DisplayFlag,
     title:                              String,
     sizeX:                              usize,
     sizeY:                              usize,
-    mapOfFrames:                        Arc<Mutex<Option<Box<[FrameID]>>>>,
+    mapOfFrames:                        Arc<Mutex<Option<FrameMap>>>,
     focus:                              Arc<Mutex<Box<FrameID>>>,
   )
   {
+    self.flags                          =                                       flags;
+    if ( flags & DisplayFlag::RestoreOnPanic ) != DisplayFlag::None
+    {
+      signal::restoreOnPanic();
+    }
+    if ( flags & DisplayFlag::RestoreOnTermination ) != DisplayFlag::None
+    {
+      signal::restoreOnTermination();
+    }
     unsafe
     {
       let mut termios                   =                                       self.termios.clone();
@@ -326,8 +360,8 @@ impl TTYDisplay
         (
           Event::new
           (
-            EventType::Error("cannot enter raw mode"),
-            display,                    0,
+            EventType::Error(Error::lastOSError(display, "cannot enter raw mode")),
+            display,                    FrameID::None,
             0,                          0,
             MouseButton::None,
           )
@@ -343,8 +377,36 @@ impl TTYDisplay
           (
             Event::new
             (
-              EventType::Error("cannot make input non-blocking"),
-              display,                  0,
+              EventType::Error(Error::lastOSError(display, "cannot make input non-blocking")),
+              display,                  FrameID::None,
+              0,                        0,
+              MouseButton::None,
+            )
+          ).unwrap();
+        }
+        self.kitty                      =                                       ( ( flags & DisplayFlag::KittyKeyboard ) != DisplayFlag::None )
+                                        &&                                      Self::queryKittyKeyboard ( &mut self.output, &mut input, &events, display );
+        self.registered
+        = signal::registerTerminal
+          (
+            Terminal
+            {
+              input:                    fd,
+              output:                   self.output.as_raw_fd(),
+              termios:                  self.termios,
+              fcntl:                    self.fcntl,
+              reset:                    ( flags & DisplayFlag::ResetTerminal ) != DisplayFlag::None,
+              kitty:                    self.kitty,
+            }
+          );
+        if self.registered.is_none()
+        {
+          events.send
+          (
+            Event::new
+            (
+              EventType::Warning("too many terminals to be restored after a crash"),
+              display,                  FrameID::None,
               0,                        0,
               MouseButton::None,
             )
@@ -352,49 +414,47 @@ impl TTYDisplay
         }
       }
     }
-    let error
-    = write!
-      (
-        self.output,
-        "{}]0;{}\x07{}{}J{}{};{}H{}?{}l{}?{}h{}?{}h",
-        TTY_ESC,                        title,
-        TTY_CSI,                        2,
-        TTY_CSI,                        1,        1,
-        TTY_CSI,                        25,
-        TTY_CSI,                        1003,
-        TTY_CSI,                        1006,
-      );
-    if !error.is_ok()
+    self.changeTitle ( events.clone(), display, title );
+    self.enterScreen ( &events, display );
+    let wakeup                          =                                       match openPipe()
     {
-      events.send
-      (
-        Event::new
+      Ok(wakeup)                        =>                                      wakeup,
+      Err(error)                        =>
+      {
+        events.send
         (
-          EventType::Error("cannot send to tty"),
-          display,                      0,
-          0,                            0,
-          MouseButton::None,
-        )
-      ).unwrap();
-    }
-    let error                           =                                       self.output.flush();
-    if !error.is_ok()
+          Event::new
+          (
+            EventType::Error(Error::IO(display, "cannot create wake-up pipe", error)),
+            display,                    FrameID::None,
+            0,                          0,
+            MouseButton::None,
+          )
+        ).unwrap();
+        return;
+      },
+    };
+    self.wakeup                         =                                       Some(wakeup);
+    if !signal::registerWakeup ( wakeup.1 )
     {
       events.send
       (
         Event::new
         (
-          EventType::Error("cannot flush to tty"),
-          display,                      0,
+          EventType::Warning("too many terminals to be notified about resizing"),
+          display,                      FrameID::None,
           0,                            0,
           MouseButton::None,
         )
       ).unwrap();
     }
     let input                           =                                       self.input.clone();
-    let ( sender, receiver ): ( Sender<bool>, Receiver<bool> )
-                                        =                                       channel();
-    self.messages                       =                                       Some(sender);
+    let resized                         =                                       self.resized.clone();
+    let resumed                         =                                       self.resumed.clone();
+    let escapeTimeout                   =                                       self.escapeTimeout.clone();
+    let suspendOnCtrlZ: bool            =                                       ( flags & DisplayFlag::SuspendOnCtrlZ ) != DisplayFlag::None;
+    let mut sizeX                       =                                       sizeX;
+    let mut sizeY                       =                                       sizeY;
     self.listener
     = Some
       (
@@ -404,42 +464,222 @@ impl TTYDisplay
           {
             if let Ok(mut input) = input.lock()
             {
-              let mut input             =                                       input.by_ref().bytes();
-              let mut state: TTYState   =                                       TTYState::ExpectByte;
-              let mut mouseState        =                                       MouseButton::None;
-              let mut listOfParameters: Vec<usize>
-                                        =                                       vec!();
-              let mut currentParameter: usize
-                                        =                                       0;
-              let mut parameterPrefix: TTYPrefix
-                                        =                                       TTYPrefix::None;
+              let mut listOfBytes       =                                       [ 0u8; 1024 ];
+              let mut parser: InputParser
+                                        =                                       InputParser::new();
+              parser.display            =                                       display;
+              let mut escapeDeadline: Option<( Instant, Instant )>
+                                        =                                       None;         // ( when the escape was received, when it is a key of its own )
+              let mut pasteDeadline: Option<( Instant, Instant )>
+                                        =                                       None;         // ( when pasted bytes were received last, when the text so far is delivered )
+              let mut listOfFds
+              = [
+                  libc::pollfd { fd: input.as_raw_fd(), events: libc::POLLIN, revents: 0 },
+                  libc::pollfd { fd: wakeup.0,          events: libc::POLLIN, revents: 0 },
+                ];
               'recvLoop:
                 loop
                 {
-                  for event             in                                      receiver.try_iter()
+                  //sleeps until there is either input or a message in the pipe,
+                  //  but not longer than it takes to tell the escape key from the begin of a sequence or to give up on the end of a paste.
+                  let timeout: libc::c_int
+                  = match escapeDeadline.or ( pasteDeadline )
+                    {
+                      Some(( _, deadline ))
+                                        =>                                      deadline.saturating_duration_since ( Instant::now() ).as_nanos().div_ceil ( 1_000_000 ) as libc::c_int,
+                      None              =>                                      -1,
+                    };
+                  let count             =                                       unsafe { libc::poll ( listOfFds.as_mut_ptr(), listOfFds.len() as libc::nfds_t, timeout ) };
+                  if count == 0
                   {
-                    if event
+                    for deadline        in                                      [ &mut escapeDeadline, &mut pasteDeadline ]
                     {
-                      break 'recvLoop;
+                      if let Some(( received, until )) = *deadline
+                      {
+                        if until <= Instant::now()
+                        {
+                          *deadline     =                                       None;
+                          if let Some(event) = parser.timeout()
+                          {
+                            events.send ( routeEvent ( event, received, &focus, &mapOfFrames ) ).unwrap();
+                          }
+                        }
+                      }
+                    }
+                    continue;
+                  }
+                  if count < 0
+                  {
+                    let error           =                                       io::Error::last_os_error();
+                    if error.kind() == io::ErrorKind::Interrupted
+                    {
+                      continue;
                     }
+                    events.send
+                    (
+                      Event::new
+                      (
+                        EventType::Error(Error::IO(display, "cannot wait for input", error)),
+                        display,        FrameID::None,
+                        0,              0,
+                        MouseButton::None,
+                      )
+                    ).unwrap();
+                    break 'recvLoop;
                   }
-                  if let Some(byte) = input.next()
+                  if listOfFds [ 1 ].revents != 0
                   {
-                    if let Ok(byte) = byte
+                    let mut message     =                                       [ 0u8; 16 ];
+                    let length
+                    = unsafe
+                      {
+                        libc::read ( wakeup.0, message.as_mut_ptr() as *mut libc::c_void, message.len() )
+                      };
+                    let message         =                                       &message [ .. length.max ( 0 ) as usize ];
+                    if message.contains ( &TTY_WAKEUP_QUIT )
+                    {
+                      break 'recvLoop;
+                    }
+                    if message.contains ( &TTY_WAKEUP_RESIZE )
+                    {
+                      let mut winsize: libc::winsize
+                                        =                                       unsafe { mem::zeroed() };
+                      if unsafe { libc::ioctl ( input.as_raw_fd(), libc::TIOCGWINSZ, &mut winsize as *mut _ ) } < 0
+                      {
+                        events.send
+                        (
+                          Event::new
+                          (
+                            EventType::Error(Error::lastOSError(display, "cannot get terminal size")),
+                            display,    FrameID::None,
+                            0,          0,
+                            MouseButton::None,
+                          )
+                        ).unwrap();
+                      }
+                      else if ( winsize.ws_col as usize, winsize.ws_row as usize ) != ( sizeX, sizeY )
+                      {
+                        //the signal is shared by all terminals, so only those with a new size are reported.
+                        sizeX           =                                       winsize.ws_col as usize;
+                        sizeY           =                                       winsize.ws_row as usize;
+                        if let Ok(mut resized) = resized.lock()
+                        {
+                          *resized      =                                       Some(( sizeX, sizeY ));
+                        }
+                        events.send
+                        (
+                          Event::new
+                          (
+                            EventType::Resize { width: sizeX, height: sizeY },
+                            display,    FrameID::None,
+                            0,          0,
+                            MouseButton::None,
+                          )
+                        ).unwrap();
+                      }
+                    }
+                    if message.contains ( &TTY_WAKEUP_SUSPEND )
+                    {
+                      events.send
+                      (
+                        Event::new
+                        (
+                          EventType::Suspended,
+                          display,      FrameID::None,
+                          0,            0,
+                          MouseButton::None,
+                        )
+                      ).unwrap();
+                    }
+                    if message.contains ( &TTY_WAKEUP_CONTINUE )
                     {
-                      state.nextState
+                      resumed.store ( true, Ordering::SeqCst );
+                      events.send
                       (
-                        byte,
-                        Some(&focus),
-                        display,
-                        Some(&events),
-                        Some(&mapOfFrames),
-                        sizeX,          sizeY,
-                        &mut mouseState,
-                        &mut listOfParameters,
-                        &mut currentParameter,
-                        &mut parameterPrefix,
-                      );
+                        Event::new
+                        (
+                          EventType::Resumed,
+                          display,      FrameID::None,
+                          0,            0,
+                          MouseButton::None,
+                        )
+                      ).unwrap();
+                    }
+                  }
+                  if listOfFds [ 0 ].revents != 0
+                  {
+                    match input.read ( &mut listOfBytes )
+                    {
+                      Ok(0)             =>
+                      {
+                        //end of input, polling again would return immediately forever.
+                        events.send
+                        (
+                          Event::new
+                          (
+                            EventType::Error(Error::IO(display, "cannot receive from tty", io::ErrorKind::UnexpectedEof.into())),
+                            display,    FrameID::None,
+                            0,          0,
+                            MouseButton::None,
+                          )
+                        ).unwrap();
+                        break 'recvLoop;
+                      },
+                      Ok(length)        =>
+                      {
+                        let received: Instant
+                                        =                                       Instant::now();
+                        for event       in                                      parser.parse ( &listOfBytes [ .. length ] )
+                        {
+                          //ctrl-z suspends right where it is, after the keys before it, however the terminal encoded it.
+                          //  pasted text is a single event, so a pasted ctrl-z does not suspend.
+                          if suspendOnCtrlZ
+                          && matches!( event.event, EventType::Ctrl('z') )
+                          && event.action != KeyAction::Release
+                          {
+                            TTYDisplay::suspend();
+                            continue;
+                          }
+                          events.send ( routeEvent ( event, received, &focus, &mapOfFrames ) ).unwrap();
+                        }
+                        escapeDeadline
+                        = if parser.isAmbiguous()
+                          {
+                            escapeDeadline.or ( Some ( ( received, received + escapeTimeout.lock().map ( | timeout | *timeout ).unwrap_or_default() ) ) )
+                          }
+                          else
+                          {
+                            None
+                          };
+                        //pasted text may come in slowly, e.g. over ssh, so every read gives the rest of it more time.
+                        pasteDeadline
+                        = if parser.isPasting()
+                          {
+                            Some ( ( received, received + Duration::from_millis ( TTY_PASTE_TIMEOUT ) ) )
+                          }
+                          else
+                          {
+                            None
+                          };
+                      },
+                      Err(ref error)
+                      if error.kind() == io::ErrorKind::WouldBlock
+                      || error.kind() == io::ErrorKind::Interrupted
+                                        =>                                      {},
+                      Err(error)        =>
+                      {
+                        events.send
+                        (
+                          Event::new
+                          (
+                            EventType::Error(Error::IO(display, "cannot receive from tty", error)),
+                            display,    FrameID::None,
+                            0,          0,
+                            MouseButton::None,
+                          )
+                        ).unwrap();
+                        break 'recvLoop;
+                      },
                     }
                   }
                 }
@@ -449,56 +689,241 @@ impl TTYDisplay
       );
   }
 
-  pub fn turnOff
+  //clears the screen, hides the cursor and enables the mouse and focus reports, whenever the display is turned on or continued.
+  fn enterScreen
   (
     &mut self,
     events:                             &EventSender,
     display:                            DisplayID,
   )
   {
+    //the alternate screen keeps the content and scrollback of the main screen untouched.
+    let screen: String
+    = if ( self.flags & DisplayFlag::ResetTerminal ) != DisplayFlag::None
+      {
+        String::new()
+      }
+      else
+      {
+        format!( "{}?{}h", TTY_CSI, 1049 )
+      };
+    //pushed onto the stack of the terminal, so turning off only pops it again.
+    let keyboard: String
+    = if self.kitty
+      {
+        format!( "{}>{}u", TTY_CSI, TTY_KITTY_FLAGS )
+      }
+      else
+      {
+        String::new()
+      };
     let error
     = write!
       (
         self.output,
-        "{}c",
-        TTY_ESC,
+        "{}{}{}{}J{}{};{}H{}?{}l{}?{}h{}?{}h{}?{}h{}?{}h",
+        screen,
+        keyboard,
+        TTY_CSI,                        2,
+        TTY_CSI,                        1,        1,
+        TTY_CSI,                        25,
+        TTY_CSI,                        1003,
+        TTY_CSI,                        1006,
+        TTY_CSI,                        2004,
+        TTY_CSI,                        1004,
       );
-    if !error.is_ok()
+    //the screen is empty now, so everything has to be sent again.
+    self.front.clear();
+    if let Err(error) = error
     {
       events.send
       (
         Event::new
         (
-          EventType::Error("cannot send to tty"),
-          display,                      0,
+          EventType::Error(Error::IO(display, "cannot send to tty", error)),
+          display,                      FrameID::None,
           0,                            0,
           MouseButton::None,
         )
       ).unwrap();
     }
     let error                           =                                       self.output.flush();
-    if !error.is_ok()
+    if let Err(error) = error
+    {
+      events.send
+      (
+        Event::new
+        (
+          EventType::Error(Error::IO(display, "cannot flush to tty", error)),
+          display,                      FrameID::None,
+          0,                            0,
+          MouseButton::None,
+        )
+      ).unwrap();
+    }
+  }
+
+  //asks for the flags of the kitty keyboard protocol and then for the device attributes,
+  //  which every terminal answers, so a terminal without the protocol does not have to time out.
+  //  keys pressed in the meantime are passed on.
+  fn queryKittyKeyboard
+  (
+    output:                             &mut Box<WriteableFd>,
+    input:                              &mut Box<ReadableFd>,
+    events:                             &EventSender,
+    display:                            DisplayID,
+  ) -> bool
+  {
+    let error
+    = write!( output, "{}?u{}c", TTY_CSI, TTY_CSI )
+        .and_then ( | _ | output.flush() );
+    if let Err(error) = error
     {
       events.send
       (
         Event::new
         (
-          EventType::Error("cannot flush to tty"),
-          display,                      0,
+          EventType::Error(Error::IO(display, "cannot send to tty", error)),
+          display,                      FrameID::None,
           0,                            0,
           MouseButton::None,
         )
       ).unwrap();
+      return false;
+    }
+    let deadline: Instant               =                                       Instant::now() + Duration::from_millis ( TTY_KITTY_TIMEOUT );
+    let mut listOfBytes                 =                                       [ 0u8; 256 ];
+    let mut parser: InputParser         =                                       InputParser::new();
+    parser.display                      =                                       display;
+    let mut supported: bool             =                                       false;
+    loop
+    {
+      let timeout: Duration             =                                       deadline.saturating_duration_since ( Instant::now() );
+      if timeout.is_zero()
+      {
+        events.send
+        (
+          Event::new
+          (
+            EventType::Warning("terminal did not answer the keyboard protocol query"),
+            display,                    FrameID::None,
+            0,                          0,
+            MouseButton::None,
+          )
+        ).unwrap();
+        return supported;
+      }
+      let mut listOfFds                 =                                       [ libc::pollfd { fd: input.as_raw_fd(), events: libc::POLLIN, revents: 0 } ];
+      let count                         =                                       unsafe { libc::poll ( listOfFds.as_mut_ptr(), 1, timeout.as_nanos().div_ceil ( 1_000_000 ) as libc::c_int ) };
+      if count <= 0
+      {
+        continue;
+      }
+      let length: usize
+      = match input.read ( &mut listOfBytes )
+        {
+          Ok(0)                         =>                                      return supported,
+          Ok(length)                    =>                                      length,
+          Err(ref error)
+          if error.kind() == io::ErrorKind::WouldBlock
+          || error.kind() == io::ErrorKind::Interrupted
+                                        =>                                      continue,
+          Err(_)                        =>                                      return supported,
+        };
+      for answer                        in                                      parser.parse ( &listOfBytes [ .. length ] )
+      {
+        match answer.event
+        {
+          EventType::KeyboardProtocol(_)
+                                        =>                                      supported = true,
+          EventType::DeviceAttributes   =>                                      return supported,
+          _                             =>                                      events.send(answer).unwrap(),
+        }
+      }
+    }
+  }
+
+  pub fn turnOff
+  (
+    &mut self,
+    events:                             &EventSender,
+    display:                            DisplayID,
+  )
+  {
+    //the panic hook or a signal handler might have restored the terminal already.
+    let restored: bool                  =                                       self.registered.take().is_some_and ( | index | !signal::unregisterTerminal ( index ) );
+    if !restored
+    {
+      let sequence: &str
+      = if ( self.flags & DisplayFlag::ResetTerminal ) != DisplayFlag::None
+        {
+          TTY_RESET_TERMINAL
+        }
+        else
+        {
+          TTY_RESTORE_SCREEN
+        };
+      let keyboard: &str
+      = if self.kitty
+        {
+          TTY_POP_KEYBOARD
+        }
+        else
+        {
+          ""
+        };
+      let error                         =                                       write!( self.output, "{}{}", keyboard, sequence );
+      if let Err(error) = error
+      {
+        events.send
+        (
+          Event::new
+          (
+            EventType::Error(Error::IO(display, "cannot send to tty", error)),
+            display,                    FrameID::None,
+            0,                          0,
+            MouseButton::None,
+          )
+        ).unwrap();
+      }
+      let error                         =                                       self.output.flush();
+      if let Err(error) = error
+      {
+        events.send
+        (
+          Event::new
+          (
+            EventType::Error(Error::IO(display, "cannot flush to tty", error)),
+            display,                    FrameID::None,
+            0,                          0,
+            MouseButton::None,
+          )
+        ).unwrap();
+      }
     }
-    if let Some(ref messages) = self.messages
+    if let Some(wakeup) = self.wakeup
     {
-      messages.send(true).unwrap();
+      signal::writePipe ( wakeup.1, TTY_WAKEUP_QUIT );
     }
     let listener: Option<JoinHandle<()>>
                                         =                                       self.listener.take();
     if let Some(listener) = listener
     {
-      listener.join().unwrap();
+      //a listener, that panicked, is gone anyway.
+      let _                             =                                       listener.join();
+    }
+    if let Some(wakeup) = self.wakeup.take()
+    {
+      signal::unregisterWakeup ( wakeup.1 );
+      unsafe
+      {
+        libc::close ( wakeup.0 );
+        libc::close ( wakeup.1 );
+      }
+    }
+    if restored
+    {
+      return;
     }
     unsafe
     {
@@ -511,8 +936,8 @@ impl TTYDisplay
           (
             Event::new
             (
-              EventType::Error("cannot reset input to blocking"),
-              display,                  0,
+              EventType::Error(Error::lastOSError(display, "cannot reset input to blocking")),
+              display,                  FrameID::None,
               0,                        0,
               MouseButton::None,
             )
@@ -525,8 +950,8 @@ impl TTYDisplay
         (
           Event::new
           (
-            EventType::Error("cannot enter cooked mode"),
-            display,                    0,
+            EventType::Error(Error::lastOSError(display, "cannot enter cooked mode")),
+            display,                    FrameID::None,
             0,                          0,
             MouseButton::None,
           )
@@ -535,6 +960,85 @@ impl TTYDisplay
     }
   }
 
+  //like ttimeoutlen in vim: a lone ESC is the escape key, if nothing follows within this time.
+  pub fn setEscapeTimeout
+  (
+    &mut self,
+    timeout:                            Duration,
+  )
+  {
+    if let Ok(mut escapeTimeout) = self.escapeTimeout.lock()
+    {
+      *escapeTimeout                    =                                       timeout;
+    }
+  }
+
+  //stops the process group, until it is continued, e.g. by fg in the shell.
+  pub fn suspend
+  (
+  )
+  {
+    signal::suspend();
+  }
+
+  //whether the process was continued or a panic was caught, and the screen has to be set up again.
+  pub fn takeResume
+  (
+    &mut self,
+  ) -> bool
+  {
+    let recovered: bool                 =                                       self.registered.is_some_and ( signal::recoverTerminal );
+    self.resumed.swap ( false, Ordering::SeqCst ) || recovered
+  }
+
+  //the terminal is in raw mode again, but whatever the shell did to the screen has to be undone.
+  pub fn resume
+  (
+    &mut self,
+    events:                             &EventSender,
+    display:                            DisplayID,
+  )
+  {
+    self.enterScreen ( events, display );
+  }
+
+  //the size the listener got after the terminal was resized, unless it has been taken already.
+  pub fn takeResize
+  (
+    &mut self,
+  ) -> Option<( usize, usize )>
+  {
+    self.resized.lock().ok().and_then ( | mut resized | resized.take() )
+  }
+
+  pub fn resize
+  (
+    &mut self,
+    events:                             &EventSender,
+    display:                            DisplayID,
+    sizeX:                              usize,
+    sizeY:                              usize,
+  )
+  {
+    //whatever the terminal made of the old content, it is cleared, so the front buffer matches it again.
+    self.front.resize ( sizeX, sizeY );
+    self.back.resize  ( sizeX, sizeY );
+    let error                           =                                       write!( self.output, "{}2J", TTY_CSI );
+    if let Err(error) = error
+    {
+      events.send
+      (
+        Event::new
+        (
+          EventType::Error(Error::IO(display, "cannot send to tty", error)),
+          display,                      FrameID::None,
+          0,                            0,
+          MouseButton::None,
+        )
+      ).unwrap();
+    }
+  }
+
   pub fn flush
   (
     &mut self,
@@ -542,15 +1046,34 @@ impl TTYDisplay
     display:                            DisplayID,
   )
   {
+    let sequence                        =                                       self.difference();
+    if !sequence.is_empty()
+    {
+      let error                         =                                       self.output.write_all( sequence.as_bytes() );
+      if let Err(error) = error
+      {
+        events.send
+        (
+          Event::new
+          (
+            EventType::Error(Error::IO(display, "cannot send to tty", error)),
+            display,                    FrameID::None,
+            0,                          0,
+            MouseButton::None,
+          )
+        ).unwrap();
+      }
+      self.front.clone_from( &self.back );
+    }
     let error                           =                                       self.output.flush();
-    if !error.is_ok()
+    if let Err(error) = error
     {
       events.send
       (
         Event::new
         (
-          EventType::Error("cannot flush to tty"),
-          display,                      0,
+          EventType::Error(Error::IO(display, "cannot flush to tty", error)),
+          display,                      FrameID::None,
           0,                            0,
           MouseButton::None,
         )
@@ -558,7 +1081,6 @@ impl TTYDisplay
     }
   }
 
-  #[allow(unused_variables)]
   pub fn drawStatusFrame
   (
     &mut self,
@@ -574,49 +1096,9 @@ impl TTYDisplay
     cutY:                               usize,
   )
   {
-    let empty                           =                                       this.bgChar.to_string().repeat( lenX );
-    write!
-    (
-      self.output,
-      "{}{};{}H{}",
-      TTY_CSI,
-      ( minY + 1 ) as u16,              ( minX + 1 ) as u16,
-      empty,
-    ).unwrap();
-    let mut offs:                 isize =                                       this.offs;
-    let mut shift:                usize =                                       0;
-    if offs < 0
-    {
-      shift                             =                                       -offs as usize;
-      offs                              =                                       0;
-    }
-    if shift < lenX
-    {
-      let offs:                   usize =                                       offs as usize + cutX;
-      write!
-      (
-        self.output,
-        "{}{};{}H",
-        TTY_CSI,
-        ( minY + 1          ) as u16,   ( minX + 1  + shift ) as u16,
-      ).unwrap();
-      for char                          in                                      this.text.chars().skip( offs ).take( lenX )
-      {
-        if char == '\x1b'
-        {
-          break;
-        }
-        write!
-        (
-          self.output,
-          "{}",
-          char,
-        ).unwrap();
-      }
-    }
+    self.back.drawStatusFrame ( this, events, lenX, lenY, minX, minY, maxX, maxY, cutX, cutY );
   }
 
-  #[allow(unused_variables)]
   pub fn drawTextFrame
   (
     &mut self,
@@ -632,72 +1114,9 @@ impl TTYDisplay
     cutY:                               usize,
   )
   {
-    let empty                           =                                       this.bgChar.to_string().repeat( lenX );
-    {
-      let posX: u16                     =                                       ( minX + 1 ) as u16;
-      for posY                          in                                      ( minY + 1 ) as u16 .. ( maxY + 1 ) as u16
-      {
-        write!
-        (
-          self.output,
-          "{}{};{}H{}",
-          TTY_CSI,
-          posY,                         posX,
-          empty,
-        ).unwrap();
-      }
-    }
-
-    let mut offsX:  isize               =                                       this.offsX;
-    let mut shiftX: usize               =                                       0;
-    if offsX < 0
-    {
-      shiftX                            =                                       -offsX as usize;
-      offsX                             =                                       0;
-    }
-    if shiftX < lenX
-    {
-      let offsX:      usize             =                                       offsX as usize + cutX;
-      let mut offsY:  isize             =                                       this.offsY;
-      let mut shiftY: usize             =                                       0;
-      if offsY < 0
-      {
-        shiftY                          =                                       -offsY as usize;
-        offsY                           =                                       0;
-      }
-      if shiftY < lenY
-      {
-        let offsY:                usize =                                       offsY as usize + cutY;
-        let posX:                 usize =                                       minX + shiftX + 1;
-        let posY:                 usize =                                       minY + shiftY + 1;
-        for ( index, line )             in                                      this.lines.iter().skip( offsY ).take( lenY ).enumerate()
-        {
-          write!
-          (
-            self.output,
-            "{}{};{}H",
-            TTY_CSI,
-            ( posY + index ) as u16,    ( posX         ) as u16,
-          ).unwrap();
-          for char                      in                                      line.chars().skip( offsX ).take( lenX )
-          {
-            if char == '\x1b'
-            {
-              break;
-            }
-            write!
-            (
-              self.output,
-              "{}",
-              char,
-            ).unwrap();
-          }
-        }
-      }
-    }
+    self.back.drawTextFrame   ( this, events, lenX, lenY, minX, minY, maxX, maxY, cutX, cutY );
   }
 
-  #[allow(unused_variables)]
   pub fn drawEditorFrame
   (
     &mut self,
@@ -713,200 +1132,9 @@ impl TTYDisplay
     cutY:                               usize,
   )
   {
-    let empty                           =                                       this.bgChar.to_string().repeat( lenX );
-    {
-      let posX:                     u16 =                                       ( minX + 1 ) as u16;
-      for posY                          in                                      ( minY + 1 ) as u16 .. ( maxY + 1 ) as u16
-      {
-        write!
-        (
-          self.output,
-          "{}{};{}H{}",
-          TTY_CSI,
-          posY,                         posX,
-          empty,
-        ).unwrap();
-      }
-    }
-
-    let mut offsX:                isize =                                       this.offsX;
-    let mut shiftX:               usize =                                       0;
-    if offsX < 0
-    {
-      shiftX                            =                                       -offsX as usize;
-      offsX                             =                                       0;
-    }
-    if shiftX < lenX
-    {
-      let offsX:                  usize =                                       offsX as usize + cutX;
-      let mut offsY:              isize =                                       this.offsY;
-      let mut shiftY:             usize =                                       0;
-      if offsY < 0
-      {
-        shiftY                          =                                       -offsY as usize;
-        offsY                           =                                       0;
-      }
-      if shiftY < lenY
-      {
-        let offsY:                usize =                                       offsY as usize + cutY;
-        let posX:                 usize =                                       minX + shiftX + 1;
-        let posY:                 usize =                                       minY + shiftY + 1;
-        for ( index, words )            in                                      this.lines.iter().skip( offsY ).take( lenY ).enumerate()
-        {
-          write!
-          (
-            self.output,
-            "{}{};{}H",
-            TTY_CSI,
-            ( posY + index ) as u16,    ( posX         ) as u16,
-          ).unwrap();
-          for word                      in                                      words
-          {
-            //most of them will be ignored by most of the terminals :'(
-            let mut style               =                                       "".to_string();
-            if ( word.font > 0 ) && ( word.font < 10 )                          { style = format!("{};", word.font + 10 ) }
-            if ( word.flags & Style::Italic          ) != Style::None                     { style.push_str( "3;") }
-            if ( word.flags & Style::Underline       ) != Style::None           { style.push_str( "4;") }
-            if ( word.flags & Style::SlowBlink       ) != Style::None           { style.push_str( "5;") }
-            if ( word.flags & Style::RapidBlink      ) != Style::None           { style.push_str( "6;") }
-            if ( word.flags & Style::Inverse         ) != Style::None           { style.push_str( "7;") }
-            if ( word.flags & Style::Conceal         ) != Style::None           { style.push_str( "8;") }
-            if ( word.flags & Style::CrossedOut      ) != Style::None           { style.push_str( "9;") }
-            if ( word.flags & Style::Fraktur         ) != Style::None           { style.push_str("20;") }
-            if ( word.flags & Style::DoubleUnderline ) != Style::None           { style.push_str("21;") }
-            if ( word.flags & Style::Framed          ) != Style::None           { style.push_str("51;") }
-            if ( word.flags & Style::Encircled       ) != Style::None           { style.push_str("52;") }
-            if ( word.flags & Style::Overlined       ) != Style::None           { style.push_str("53;") }
-            let fgColour
-            = match word.fgColour
-              {
-                Colour::RGB( red, green, blue )                                 => { format!( "38;2;{};{};{}", red, green, blue) },
-                Colour::Standard( colour )                      if colour < 8
-                                                                                => { format!( "38;5;{}", colour ) },
-                Colour::Bright( colour )                        if colour < 8
-                                                                                => { format!( "38;5;{}", colour + 8 ) },
-                Colour::Cube( red, green, blue )                if red    < 6
-                                                                && green  < 6
-                                                                && blue   < 6
-                                                                                => { format!( "38;5;{}", 16 + 36 * red + 6 * green + blue) },
-                Colour::Grey( colour )                          if colour < 24
-                                                                                => { format!( "38;5;{}", colour + 232 ) },
-                Colour::Default                                                 => {   "39".to_string() },
-                Colour::Black                                                   => {   "30".to_string() },
-                Colour::Red                                                     => {   "31".to_string() },
-                Colour::Green                                                   => {   "32".to_string() },
-                Colour::Brown                                                   => {   "33".to_string() },
-                Colour::Blue                                                    => {   "34".to_string() },
-                Colour::Purple                                                  => {   "35".to_string() },
-                Colour::Cyan                                                    => {   "36".to_string() },
-                Colour::LightGrey                                               => {   "37".to_string() },
-                Colour::DarkGrey                                                => { "1;30".to_string() },
-                Colour::LightRed                                                => { "1;31".to_string() },
-                Colour::LightGreen                                              => { "1;32".to_string() },
-                Colour::Yellow                                                  => { "1;33".to_string() },
-                Colour::LightBlue                                               => { "1;34".to_string() },
-                Colour::LightPurple                                             => { "1;35".to_string() },
-                Colour::LightCyan                                               => { "1;36".to_string() },
-                Colour::White                                                   => { "1;37".to_string() },
-                Colour::FaintBlack                                              => { "2;30".to_string() },
-                Colour::FaintRed                                                => { "2;31".to_string() },
-                Colour::FaintGreen                                              => { "2;32".to_string() },
-                Colour::FaintYellow                                             => { "2;33".to_string() },
-                Colour::FaintBlue                                               => { "2;34".to_string() },
-                Colour::FaintPurple                                             => { "2;35".to_string() },
-                Colour::FaintCyan                                               => { "2;36".to_string() },
-                Colour::FaintWhite                                              => { "2;37".to_string() },
-                Colour::BrightBlack                                             => {   "90".to_string() },
-                Colour::BrightRed                                               => {   "91".to_string() },
-                Colour::BrightGreen                                             => {   "92".to_string() },
-                Colour::BrightYellow                                            => {   "93".to_string() },
-                Colour::BrightBlue                                              => {   "94".to_string() },
-                Colour::BrightPurple                                            => {   "95".to_string() },
-                Colour::BrightCyan                                              => {   "96".to_string() },
-                Colour::BrightWhite                                             => {   "97".to_string() },
-                _                                                               => {     "".to_string() },
-              };
-            let bgColour
-            = match word.bgColour
-              {
-                Colour::RGB( red, green, blue )                                 => { format!( "48;2;{};{};{}", red, green, blue ) },
-                Colour::Standard( colour )                      if colour < 8
-                                                                                => { format!( "48;5;{}", colour ) },
-                Colour::Bright( colour )                        if colour < 8
-                                                                                => { format!( "48;5;{}", colour + 8 ) },
-                Colour::Cube( red, green, blue )                if red    < 6
-                                                                && green  < 6
-                                                                && blue   < 6
-                                                                                => { format!( "48;5;{}", 16 + 36 * red + 6 * green + blue) },
-                Colour::Grey( colour )                          if colour < 24
-                                                                                => { format!( "48;5;{}", colour + 232 ) },
-                Colour::Default                                                 => {   "49".to_string() },
-                Colour::Black                                                   => {   "40".to_string() },
-                Colour::Red                                                     => {   "41".to_string() },
-                Colour::Green                                                   => {   "42".to_string() },
-                Colour::Brown                                                   => {   "43".to_string() },
-                Colour::Blue                                                    => {   "44".to_string() },
-                Colour::Purple                                                  => {   "45".to_string() },
-                Colour::Cyan                                                    => {   "46".to_string() },
-                Colour::LightGrey                                               => {   "47".to_string() },
-                Colour::DarkGrey                                                => { "1;40".to_string() },
-                Colour::LightRed                                                => { "1;41".to_string() },
-                Colour::LightGreen                                              => { "1;42".to_string() },
-                Colour::Yellow                                                  => { "1;43".to_string() },
-                Colour::LightBlue                                               => { "1;44".to_string() },
-                Colour::LightPurple                                             => { "1;45".to_string() },
-                Colour::LightCyan                                               => { "1;46".to_string() },
-                Colour::White                                                   => { "1;47".to_string() },
-                Colour::FaintBlack                                              => { "2;40".to_string() },
-                Colour::FaintRed                                                => { "2;41".to_string() },
-                Colour::FaintGreen                                              => { "2;42".to_string() },
-                Colour::FaintYellow                                             => { "2;43".to_string() },
-                Colour::FaintBlue                                               => { "2;44".to_string() },
-                Colour::FaintPurple                                             => { "2;45".to_string() },
-                Colour::FaintCyan                                               => { "2;46".to_string() },
-                Colour::FaintWhite                                              => { "2;47".to_string() },
-                Colour::BrightBlack                                             => {  "100".to_string() },
-                Colour::BrightRed                                               => {  "101".to_string() },
-                Colour::BrightGreen                                             => {  "102".to_string() },
-                Colour::BrightYellow                                            => {  "103".to_string() },
-                Colour::BrightBlue                                              => {  "104".to_string() },
-                Colour::BrightPurple                                            => {  "105".to_string() },
-                Colour::BrightCyan                                              => {  "106".to_string() },
-                Colour::BrightWhite                                             => {  "107".to_string() },
-                _                                                               => {     "".to_string() },
-              };
-            write!
-            (
-              self.output,
-              "\x1b[{}{};{}m",
-              style,
-              fgColour,                 bgColour,
-            ).unwrap();
-            for char                    in                                      word.word.chars().skip( offsX ).take( lenX )
-            {
-              if char == '\x1b'
-              {
-                break;
-              }
-              write!
-              (
-                self.output,
-                "{}",
-                char,
-              ).unwrap();
-            }
-            write!
-            (
-              self.output,
-              "\x1b[0m",
-            ).unwrap();
-          }
-        }
-      }
-    }
+    self.back.drawEditorFrame ( this, events, lenX, lenY, minX, minY, maxX, maxY, cutX, cutY );
   }
 
-  #[allow(unused_variables)]
   pub fn drawPixelFrame
   (
     &mut self,
@@ -922,9 +1150,9 @@ impl TTYDisplay
     cutY:                               usize,
   )
   {
+    self.back.drawPixelFrame  ( this, events, lenX, lenY, minX, minY, maxX, maxY, cutX, cutY );
   }
 
-  #[allow(unused_variables)]
   pub fn drawPlotFrame
   (
     &mut self,
@@ -940,373 +1168,297 @@ impl TTYDisplay
     cutY:                               usize,
   )
   {
+    self.back.drawPlotFrame   ( this, events, lenX, lenY, minX, minY, maxX, maxY, cutX, cutY );
   }
-}
 
-impl TTYState
-{
-  pub fn nextState
+  //select graphic rendition of a cell, starting with a reset of all attributes.
+  //most of them will be ignored by most of the terminals :'(
+  fn graphicRendition
   (
-    &mut self,
-    byte:                               u8,
-    focus:                              Option<&Arc<Mutex<Box<FrameID>>>>,
-    display:                            DisplayID,
-    events:                             Option<&EventSender>,
-    mapOfFrames:                        Option<&Arc<Mutex<Option<Box<[FrameID]>>>>>,
-    width:                              usize,
-    height:                             usize,
-    mouseState:                         &mut MouseButton,
-    listOfParameters:                   &mut Vec<usize>,
-    currentParameter:                   &mut usize,
-    parameterPrefix:                    &mut TTYPrefix,
-  ) -> Option<Event>
+    cell:                               &Cell,
+  ) -> String
+  {
+    let mut listOfParameters: Vec<String>
+                                        =                                       vec!( "0".to_string() );
+    if ( cell.font > 0 ) && ( cell.font < 10 )                                  { listOfParameters.push( format!( "{}", cell.font + 10 ) ) }
+    if ( cell.flags & Style::Italic          ) != Style::None                   { listOfParameters.push(  "3".to_string() ) }
+    if ( cell.flags & Style::Underline       ) != Style::None                   { listOfParameters.push(  "4".to_string() ) }
+    if ( cell.flags & Style::SlowBlink       ) != Style::None                   { listOfParameters.push(  "5".to_string() ) }
+    if ( cell.flags & Style::RapidBlink      ) != Style::None                   { listOfParameters.push(  "6".to_string() ) }
+    if ( cell.flags & Style::Inverse         ) != Style::None                   { listOfParameters.push(  "7".to_string() ) }
+    if ( cell.flags & Style::Conceal         ) != Style::None                   { listOfParameters.push(  "8".to_string() ) }
+    if ( cell.flags & Style::CrossedOut      ) != Style::None                   { listOfParameters.push(  "9".to_string() ) }
+    if ( cell.flags & Style::Fraktur         ) != Style::None                   { listOfParameters.push( "20".to_string() ) }
+    if ( cell.flags & Style::DoubleUnderline ) != Style::None                   { listOfParameters.push( "21".to_string() ) }
+    if ( cell.flags & Style::Framed          ) != Style::None                   { listOfParameters.push( "51".to_string() ) }
+    if ( cell.flags & Style::Encircled       ) != Style::None                   { listOfParameters.push( "52".to_string() ) }
+    if ( cell.flags & Style::Overlined       ) != Style::None                   { listOfParameters.push( "53".to_string() ) }
+    let fgColour
+    = match cell.fgColour
+      {
+        Colour::RGB( red, green, blue )                                         => { format!( "38;2;{};{};{}", red, green, blue) },
+        Colour::Standard( colour )                              if colour < 8
+                                                                                => { format!( "38;5;{}", colour ) },
+        Colour::Bright( colour )                                if colour < 8
+                                                                                => { format!( "38;5;{}", colour + 8 ) },
+        Colour::Cube( red, green, blue )                        if red    < 6
+                                                                && green  < 6
+                                                                && blue   < 6
+                                                                                => { format!( "38;5;{}", 16 + 36 * red + 6 * green + blue) },
+        Colour::Grey( colour )                                  if colour < 24
+                                                                                => { format!( "38;5;{}", colour + 232 ) },
+        Colour::Default                                                         => {   "39".to_string() },
+        Colour::Black                                                           => {   "30".to_string() },
+        Colour::Red                                                             => {   "31".to_string() },
+        Colour::Green                                                           => {   "32".to_string() },
+        Colour::Brown                                                           => {   "33".to_string() },
+        Colour::Blue                                                            => {   "34".to_string() },
+        Colour::Purple                                                          => {   "35".to_string() },
+        Colour::Cyan                                                            => {   "36".to_string() },
+        Colour::LightGrey                                                       => {   "37".to_string() },
+        Colour::DarkGrey                                                        => { "1;30".to_string() },
+        Colour::LightRed                                                        => { "1;31".to_string() },
+        Colour::LightGreen                                                      => { "1;32".to_string() },
+        Colour::Yellow                                                          => { "1;33".to_string() },
+        Colour::LightBlue                                                       => { "1;34".to_string() },
+        Colour::LightPurple                                                     => { "1;35".to_string() },
+        Colour::LightCyan                                                       => { "1;36".to_string() },
+        Colour::White                                                           => { "1;37".to_string() },
+        Colour::FaintBlack                                                      => { "2;30".to_string() },
+        Colour::FaintRed                                                        => { "2;31".to_string() },
+        Colour::FaintGreen                                                      => { "2;32".to_string() },
+        Colour::FaintYellow                                                     => { "2;33".to_string() },
+        Colour::FaintBlue                                                       => { "2;34".to_string() },
+        Colour::FaintPurple                                                     => { "2;35".to_string() },
+        Colour::FaintCyan                                                       => { "2;36".to_string() },
+        Colour::FaintWhite                                                      => { "2;37".to_string() },
+        Colour::BrightBlack                                                     => {   "90".to_string() },
+        Colour::BrightRed                                                       => {   "91".to_string() },
+        Colour::BrightGreen                                                     => {   "92".to_string() },
+        Colour::BrightYellow                                                    => {   "93".to_string() },
+        Colour::BrightBlue                                                      => {   "94".to_string() },
+        Colour::BrightPurple                                                    => {   "95".to_string() },
+        Colour::BrightCyan                                                      => {   "96".to_string() },
+        Colour::BrightWhite                                                     => {   "97".to_string() },
+        _                                                                       => {     "".to_string() },
+      };
+    let bgColour
+    = match cell.bgColour
+      {
+        Colour::RGB( red, green, blue )                                         => { format!( "48;2;{};{};{}", red, green, blue ) },
+        Colour::Standard( colour )                              if colour < 8
+                                                                                => { format!( "48;5;{}", colour ) },
+        Colour::Bright( colour )                                if colour < 8
+                                                                                => { format!( "48;5;{}", colour + 8 ) },
+        Colour::Cube( red, green, blue )                        if red    < 6
+                                                                && green  < 6
+                                                                && blue   < 6
+                                                                                => { format!( "48;5;{}", 16 + 36 * red + 6 * green + blue) },
+        Colour::Grey( colour )                                  if colour < 24
+                                                                                => { format!( "48;5;{}", colour + 232 ) },
+        Colour::Default                                                         => {   "49".to_string() },
+        Colour::Black                                                           => {   "40".to_string() },
+        Colour::Red                                                             => {   "41".to_string() },
+        Colour::Green                                                           => {   "42".to_string() },
+        Colour::Brown                                                           => {   "43".to_string() },
+        Colour::Blue                                                            => {   "44".to_string() },
+        Colour::Purple                                                          => {   "45".to_string() },
+        Colour::Cyan                                                            => {   "46".to_string() },
+        Colour::LightGrey                                                       => {   "47".to_string() },
+        Colour::DarkGrey                                                        => { "1;40".to_string() },
+        Colour::LightRed                                                        => { "1;41".to_string() },
+        Colour::LightGreen                                                      => { "1;42".to_string() },
+        Colour::Yellow                                                          => { "1;43".to_string() },
+        Colour::LightBlue                                                       => { "1;44".to_string() },
+        Colour::LightPurple                                                     => { "1;45".to_string() },
+        Colour::LightCyan                                                       => { "1;46".to_string() },
+        Colour::White                                                           => { "1;47".to_string() },
+        Colour::FaintBlack                                                      => { "2;40".to_string() },
+        Colour::FaintRed                                                        => { "2;41".to_string() },
+        Colour::FaintGreen                                                      => { "2;42".to_string() },
+        Colour::FaintYellow                                                     => { "2;43".to_string() },
+        Colour::FaintBlue                                                       => { "2;44".to_string() },
+        Colour::FaintPurple                                                     => { "2;45".to_string() },
+        Colour::FaintCyan                                                       => { "2;46".to_string() },
+        Colour::FaintWhite                                                      => { "2;47".to_string() },
+        Colour::BrightBlack                                                     => {  "100".to_string() },
+        Colour::BrightRed                                                       => {  "101".to_string() },
+        Colour::BrightGreen                                                     => {  "102".to_string() },
+        Colour::BrightYellow                                                    => {  "103".to_string() },
+        Colour::BrightBlue                                                      => {  "104".to_string() },
+        Colour::BrightPurple                                                    => {  "105".to_string() },
+        Colour::BrightCyan                                                      => {  "106".to_string() },
+        Colour::BrightWhite                                                     => {  "107".to_string() },
+        _                                                                       => {     "".to_string() },
+      };
+    if !fgColour.is_empty()                                                     { listOfParameters.push( fgColour ) }
+    if !bgColour.is_empty()                                                     { listOfParameters.push( bgColour ) }
+    format!( "{}{}m", TTY_CSI, listOfParameters.join(";") )
+  }
+
+  //compare the back with the front buffer and generate the sequence, that turns the latter into the former,
+  //  moving the cursor and changing the graphic rendition only when necessary.
+  fn difference
+  (
+    &self,
+  ) -> String
   {
-    let mut returnValue: Option<Event>  =                                       None;
-    match self
+    let mut sequence                    =                                       String::new();
+    let mut cursor: Option<( usize, usize )>
+                                        =                                       None;
+    let mut rendition: Option<Cell>     =                                       None;
+    for y                               in                                      0 .. self.back.sizeY
     {
-      TTYState::ExpectByte                                                      =>
+      for x                             in                                      0 .. self.back.sizeX
       {
-        match byte
+        let cell: &Cell                 =                                       self.back.cell( x, y ).unwrap();
+        if self.front.cell( x, y ) == Some(cell)
         {
-          0x1b                                                                  =>
-          {
-            *self                       =                                       TTYState::Escape;
-          },
-          byte @ 0x20...0x7e                                                    =>
-          {
-            //println!("char: {}", byte as char);
-            let mut frame: FrameID      =                                       0;
-            if let Some(focus) = focus
-            {
-              if let Ok(focus) = focus.lock()
-              {
-                frame                   =                                       **focus;
-              }
-            }
-            let newEvent: Event
-            = Event::new
-              (
-                EventType::Character(byte as char),
-                display,                frame,
-                0,                      0,
-                *mouseState,
-              );
-            if let Some(events) = events
-            {
-              events.send(newEvent).unwrap();
-            }
-            else
-            {
-              returnValue                =                                       Some(newEvent);
-            }
-          },
-          _                                                                     =>
-          {
-          },
+          continue;
         }
-      },
-      TTYState::Escape                                                          =>
-      {
-        match byte
+        match cursor
         {
-          r @ 0x30 ... 0x39                                                     =>
+          Some(( cursorX, cursorY ))    if ( cursorX == x ) && ( cursorY == y ) =>  {},
+          Some(( cursorX, cursorY ))    if ( cursorX <  x ) && ( cursorY == y ) =>
           {
-            *parameterPrefix            =                                       TTYPrefix::Escape;
-            *listOfParameters           =                                       vec!();
-            *currentParameter           =                                       r as usize - 0x30;
-            *self                       =                                       TTYState::ParseArgument;
+            sequence.push_str( &format!( "{}{}C", TTY_CSI, x - cursorX ) );
           },
-          0x5b                                                                  =>
+          _                                                                     =>
           {
-            *self                       =                                       TTYState::CSI;
+            sequence.push_str( &format!( "{}{};{}H", TTY_CSI, y + 1, x + 1 ) );
           },
-          c @ _                                                                 =>
-          {
-            let mut frame: FrameID      =                                       0;
-            if let Some(focus) = focus
-            {
-              if let Ok(focus) = focus.lock()
-              {
-                frame                   =                                       **focus;
-              }
-            }
-            let newEvent: Event
-            = Event::new
-              (
-                EventType::Escape,
-                display,                frame,
-                0,                      0,
-                *mouseState,
-              );
-            if let Some(events) = events
-            {
-              events.send(newEvent).unwrap();
-            }
-            else
-            {
-              returnValue               =                                       Some(newEvent);
-            }
-            if c != 0x1b
-            {
-              *self                     =                                       TTYState::ExpectByte;
-            }
-          }
         }
-      },
-      TTYState::CSI                                                             =>
-      {
-        match byte
-        {
-          r @ 0x30 ... 0x39                                                     =>
-          {
-            *parameterPrefix            =                                       TTYPrefix::CSI;
-            *listOfParameters           =                                       vec!();
-            *currentParameter           =                                       r as usize - 0x30;
-            *self                       =                                       TTYState::ParseArgument;
-          },
-          0x3c                                                                  =>
+        let same
+        = match rendition
           {
-            *parameterPrefix            =                                       TTYPrefix::Mouse;
-            *listOfParameters           =                                       vec!();
-            *currentParameter           =                                       0;
-            *self                       =                                       TTYState::ParseArgument;
-          },
-          _                                                                     =>
+            Some(ref current)           =>                                      ( current.flags     == cell.flags     )
+                                                                            &&  ( current.font      == cell.font      )
+                                                                            &&  ( current.fgColour  == cell.fgColour  )
+                                                                            &&  ( current.bgColour  == cell.bgColour  ),
+            None                        =>                                      false,
+          };
+        if !same
+        {
+          sequence.push_str( &Self::graphicRendition ( cell ) );
+          rendition                     =                                       Some(*cell);
+        }
+        sequence.push( cell.character );
+        //the cursor stays in the last column instead of wrapping, and wide or combining characters move it
+        //  by two or no columns, so do not assume its position.
+        cursor
+        = if ( x + 1 < self.back.sizeX ) && isNarrow ( cell.character )
           {
-            *self                       =                                       TTYState::ExpectByte;
+            Some(( x + 1, y ))
           }
-        }
-      },
-      TTYState::ParseArgument                                                   =>
+          else
+          {
+            None
+          };
+      }
+    }
+    if rendition.is_some()
+    {
+      sequence.push_str( &format!( "{}0m", TTY_CSI ) );
+    }
+    sequence
+  }
+}
+
+impl Drop for TTYDisplay
+{
+  //turned on, but never turned off, e.g. while unwinding a panic.
+  fn drop
+  (
+    &mut self,
+  )
+  {
+    if self.listener.is_some()
+    {
+      let ( events, _receiver )         =                                       Event::openChannel();
+      self.turnOff ( &events, DisplayID::None );
+    }
+  }
+}
+
+//non-blocking pipe, so neither the listener draining it nor a writer can ever get stuck on it.
+fn openPipe
+(
+) -> io::Result<( RawFd, RawFd )>
+{
+  let mut listOfFds: [ libc::c_int; 2 ] =                                       [ -1, -1 ];
+  unsafe
+  {
+    if libc::pipe ( listOfFds.as_mut_ptr() ) < 0
+    {
+      return Err(io::Error::last_os_error());
+    }
+    for &fd                             in                                      listOfFds.iter()
+    {
+      if libc::fcntl ( fd, libc::F_SETFL, libc::fcntl ( fd, libc::F_GETFL ) | libc::O_NONBLOCK ) < 0
+      || libc::fcntl ( fd, libc::F_SETFD, libc::FD_CLOEXEC ) < 0
       {
-        match byte
+        let error                       =                                       io::Error::last_os_error();
+        libc::close ( listOfFds [ 0 ] );
+        libc::close ( listOfFds [ 1 ] );
+        return Err(error);
+      }
+    }
+  }
+  Ok(( listOfFds [ 0 ], listOfFds [ 1 ] ))
+}
+
+//mouse events go to the topmost frame under the pointer, answers of the terminal to nobody and everything else to the focused frame.
+//  the event is stamped with the time its bytes were received.
+fn routeEvent
+(
+  mut event:                            Event,
+  received:                             Instant,
+  focus:                                &Arc<Mutex<Box<FrameID>>>,
+  mapOfFrames:                          &Arc<Mutex<Option<FrameMap>>>,
+) -> Event
+{
+  event.timestamp                       =                                       received;
+  if event.isMouse()
+  {
+    let mut mapped: MappedFrame         =                                       MappedFrame::None;
+    if let Ok(mapOfFrames) = mapOfFrames.lock()
+    {
+      if let Some(ref mapOfFrames) = *mapOfFrames
+      {
+        mapped                          =                                       mapOfFrames.at ( event.cursorX, event.cursorY ).copied().unwrap_or ( MappedFrame::None );
+      }
+    }
+    event.frame                         =                                       mapped.frame;
+    //the scroll offsets of the frame are added, when the event is received.
+    event.localX                        =                                       event.cursorX as isize - mapped.posX;
+    event.localY                        =                                       event.cursorY as isize - mapped.posY;
+  }
+  else
+  {
+    match event.event
+    {
+      EventType::CursorPosition
+      | EventType::KeyboardProtocol(_)
+      | EventType::DeviceAttributes
+      | EventType::DisplayFocusGained(_)
+      | EventType::DisplayFocusLost(_)  =>                                      {},
+      _                                 =>
+      {
+        if let Ok(focus) = focus.lock()
         {
-          r @ 0x30 ... 0x39                                                     =>
-          {
-            *currentParameter           =                                       *currentParameter * 10 + r as usize - 0x30;
-          },
-          0x3b                                                                  =>
-          {
-            //println!("newArgument: {}", *currentParameter);
-            listOfParameters.push(*currentParameter);
-            *currentParameter           =                                       0;
-          },
-          0x4d  if listOfParameters.len() == 2
-                && *parameterPrefix == TTYPrefix::Mouse                         =>
-          {
-            listOfParameters.push(*currentParameter);
-            let frame: FrameID
-            = if let Some(mapOfFrames) = mapOfFrames                            //did I get a reference to a map of frames?
-              {
-                if let Ok(mapOfFrames) = mapOfFrames.lock()                     //can I access it?
-                {
-                  if let Some(ref mapOfFrames) = *mapOfFrames                   //is it not empty?
-                  {
-                    let x               =                                       listOfParameters[1] - 1;
-                    let y               =                                       listOfParameters[2] - 1;
-                    if ( x < width  )
-                    && ( y < height )
-                    {
-                      mapOfFrames[ y * width + x ]
-                    }
-                    else
-                    {
-                      0
-                    }
-                  }
-                  else
-                  {
-                    0
-                  }
-                }
-                else
-                {
-                  0
-                }
-              }
-              else
-              {
-                0
-              };
-            let mut theEvent: Option<EventType>
-                                        =                                       None;
-            match listOfParameters[0]
-            {
-              0                                                                 =>
-              {
-                *mouseState             |=                                      MouseButton::LeftDown;
-                theEvent                =                                       Some(EventType::MouseLeftButtonPressed);
-              },
-              1                                                                 =>
-              {
-                *mouseState             |=                                      MouseButton::MiddleDown;
-                theEvent                =                                       Some(EventType::MouseMiddleButtonPressed);
-              },
-              2                                                                 =>
-              {
-                *mouseState             |=                                      MouseButton::RightDown;
-                theEvent                =                                       Some(EventType::MouseRightButtonPressed);
-              },
-              32                                                                =>
-              {
-                theEvent                =                                       Some(EventType::MouseMoveWithLeftButton);
-              },
-              33                                                                =>
-              {
-                theEvent                =                                       Some(EventType::MouseMoveWithMiddleButton);
-              },
-              34                                                                =>
-              {
-                theEvent                =                                       Some(EventType::MouseMoveWithRightButton);
-              },
-              35                                                                =>
-              {
-                theEvent                =                                       Some(EventType::MouseOver);
-              }
-              64                                                                =>
-              {
-                theEvent                =                                       Some(EventType::MouseWheelUp);
-              },
-              65                                                                =>
-              {
-                theEvent                =                                       Some(EventType::MouseWheelDown);
-              },
-              _                                                                 => { /* invalid */ },
-            }
-            *self                       =                                       TTYState::ExpectByte;
-            if let Some(theEvent) = theEvent
-            {
-              let newEvent: Event
-              = Event::new
-                (
-                  theEvent,
-                  display,              frame,
-                  listOfParameters[2] - 1,
-                  listOfParameters[1] - 1,
-                  *mouseState,
-                );
-              if let Some(events) = events
-              {
-                events.send(newEvent).unwrap();
-              }
-              else
-              {
-                returnValue             =                                       Some(newEvent);
-              }
-            }
-          },
-          0x6d  if listOfParameters.len() == 2
-                && *parameterPrefix == TTYPrefix::Mouse                         =>
-          {
-            listOfParameters.push(*currentParameter);
-            let frame: FrameID
-            = if let Some(mapOfFrames) = mapOfFrames                            //did I get a reference to a map of frames?
-              {
-                if let Ok(mapOfFrames) = mapOfFrames.lock()                     //can I access it?
-                {
-                  if let Some(ref mapOfFrames) = *mapOfFrames                   //is it not empty?
-                  {
-                    let x               =                                       listOfParameters[1] - 1;
-                    let y               =                                       listOfParameters[2] - 1;
-                    if ( x < width  )
-                    && ( y < height )
-                    {
-                      mapOfFrames[ y * width + x ]
-                    }
-                    else
-                    {
-                      0
-                    }
-                  }
-                  else
-                  {
-                    0
-                  }
-                }
-                else
-                {
-                  0
-                }
-              }
-              else
-              {
-                0
-              };
-            let mut theEvent: Option<EventType>
-                                        =                                       None;
-            match listOfParameters[0]
-            {
-              0                                                                 =>
-              {
-                *mouseState             &=                                      !MouseButton::LeftDown;
-                theEvent                =                                       Some(EventType::MouseLeftButtonReleased);
-              },
-              1                                                                 =>
-              {
-                *mouseState             &=                                      !MouseButton::MiddleDown;
-                theEvent                =                                       Some(EventType::MouseMiddleButtonReleased);
-              },
-              2                                                                 =>
-              {
-                *mouseState             &=                                      !MouseButton::RightDown;
-                theEvent                =                                       Some(EventType::MouseRightButtonReleased);
-              },
-              _                                                                 => { /* invalid */ },
-            }
-            *self                       =                                       TTYState::ExpectByte;
-            if let Some(theEvent) = theEvent
-            {
-              let newEvent: Event
-              = Event::new
-                (
-                  theEvent,
-                  display,              frame,
-                  listOfParameters[2] - 1,
-                  listOfParameters[1] - 1,
-                  *mouseState,
-                );
-              if let Some(events) = events
-              {
-                events.send(newEvent).unwrap();
-              }
-              else
-              {
-                returnValue             =                                       Some(newEvent);
-              }
-            }
-          },
-          0x52  if listOfParameters.len() == 1
-                && *parameterPrefix == TTYPrefix::CSI                           =>
-          {
-            listOfParameters.push(*currentParameter);
-            //println!("size: {}x{}", listOfParameters[1], listOfParameters[0]);
-            *self                       =                                        TTYState::ExpectByte;
-            let newEvent: Event
-            = Event::new
-              (
-                EventType::CursorPosition,
-                display,              0,
-                listOfParameters[1] - 0,
-                listOfParameters[0] - 0,
-                *mouseState,
-              );
-            if let Some(events) = events
-            {
-              events.send(newEvent).unwrap();
-            }
-            else
-            {
-              returnValue               =                                       Some(newEvent);
-            }
-          },
-          _r @ _                                                                =>
-          {
-           //println!("cannot parse {:?} {}", listOfParameters, r as char);
-           *self                        =                                       TTYState::ExpectByte;
-          },
+          event.frame                   =                                       **focus;
         }
       },
     }
-    returnValue
   }
+  event
+}
+
+//characters known to take exactly one column: printable ascii, latin-1 and the latin extensions before the combining accents.
+fn isNarrow
+(
+  character:                            char,
+) -> bool
+{
+  matches!( character, ' ' ..= '~' | '\u{a0}' ..= '\u{2ff}' )
 }
@@ -0,0 +1,80 @@
+use crate::
+{
+  display::
+  {
+    DisplayID,
+  },
+  frame::
+  {
+    FrameID,
+  },
+};
+
+use std::
+{
+  error,
+  fmt,
+  io,
+};
+
+#[derive(Debug)]
+pub enum Error
+{
+  InvalidFrame        ( FrameID ),                                              // zero or never added
+  InvalidatedFrame    ( FrameID ),                                              // no longer available
+  InvalidDisplay      ( DisplayID ),                                            // zero or never added
+  InvalidatedDisplay  ( DisplayID ),                                            // no longer available
+  Poisoned            ( DisplayID ),                                            // a lock shared with the listener of the display
+  TerminalSize        ( DisplayID ),                                            // answer of the terminal was not the cursor position
+  IO                  ( DisplayID, &'static str, io::Error ),                   // ( display, what failed, why it failed )
+}
+
+impl Error
+{
+  //wraps the last error of the operating system, e.g. after a failed libc-call.
+  pub fn lastOSError
+  (
+    display:                            DisplayID,
+    action:                             &'static str,
+  ) -> Self
+  {
+    Error::IO ( display, action, io::Error::last_os_error() )
+  }
+}
+
+impl fmt::Display for Error
+{
+  fn fmt
+  (
+    &self,
+    formatter:                          &mut fmt::Formatter,
+  ) -> fmt::Result
+  {
+    match self
+    {
+      Error::InvalidFrame       ( frame )             =>                        write!( formatter, "invalid UID of frame {}",                       frame ),
+      Error::InvalidatedFrame   ( frame )             =>                        write!( formatter, "UID of frame {} invalidated",                   frame ),
+      Error::InvalidDisplay     ( display )           =>                        write!( formatter, "invalid UID of display {}",                     display ),
+      Error::InvalidatedDisplay ( display )           =>                        write!( formatter, "UID of display {} invalidated",                 display ),
+      Error::Poisoned           ( display )           =>                        write!( formatter, "display {}: lock poisoned",                     display ),
+      Error::TerminalSize       ( display )           =>                        write!( formatter, "display {}: cannot determine terminal dimensions", display ),
+      Error::IO                 ( display, action, error )
+                                                      =>                        write!( formatter, "display {}: {}: {}",                            display, action, error ),
+    }
+  }
+}
+
+impl error::Error for Error
+{
+  fn source
+  (
+    &self,
+  ) -> Option<&(dyn error::Error + 'static)>
+  {
+    match self
+    {
+      Error::IO ( _, _, ref error )     =>                                      Some(error),
+      _                                 =>                                      None,
+    }
+  }
+}
@@ -0,0 +1,199 @@
+use crate::
+{
+  event::
+  {
+    Event,
+    EventType,
+  },
+  frame::
+  {
+    FrameID,
+  },
+};
+
+use std::
+{
+  time::
+  {
+    Duration,
+    Instant,
+  },
+};
+
+const GESTURE_CLICK_INTERVAL:           u64 =                                   400;          // milliseconds
+
+//turns raw mouse events into clicks, drags and hovering, so every application does not have to.
+//  clicks are timed by the timestamps of the events, so a late call does not break a double click.
+pub struct GestureRecognizer
+{
+  pub clickInterval:                    Duration,                               // longest time between the presses of a double click
+  pub clickDistance:                    usize,                                  // cells the pointer may move between these presses
+  pub dragDistance:                     usize,                                  // cells the pointer has to move, before a drag starts
+  lastClick:                            Option<( Instant, usize, usize )>,
+  clickCount:                           usize,
+  pressed:                              Option<( FrameID, usize, usize )>,      // left button held down here
+  dragging:                             bool,
+  hovered:                              FrameID,
+  hoveredOrigin:                        ( isize, isize ),                       // where the content of the hovered frame begins on the screen
+}
+
+impl GestureRecognizer
+{
+  pub fn new
+  (
+  ) -> Self
+  {
+    Self
+    {
+      clickInterval:                    Duration::from_millis ( GESTURE_CLICK_INTERVAL ),
+      clickDistance:                    0,
+      dragDistance:                     1,
+      lastClick:                        None,
+      clickCount:                       0,
+      pressed:                          None,
+      dragging:                         false,
+      hovered:                          FrameID::None,
+      hoveredOrigin:                    ( 0, 0 ),
+    }
+  }
+
+  //the gestures recognized from this event, that follow it.
+  pub fn recognize
+  (
+    &mut self,
+    event:                              &Event,
+  ) -> Vec<Event>
+  {
+    let mut listOfGestures: Vec<Event>  =                                       vec!();
+    if !event.isMouse()
+    {
+      return listOfGestures;
+    }
+    if event.frame != self.hovered
+    {
+      //the pointer is outside of the frame left, so its position there is only known from the last event over it.
+      if !self.hovered.isNone()
+      {
+        listOfGestures.push ( gesture ( event, EventType::MouseLeave, self.hovered, self.hoveredOrigin ) );
+      }
+      if !event.frame.isNone()
+      {
+        listOfGestures.push ( gesture ( event, EventType::MouseEnter, event.frame, origin ( event ) ) );
+      }
+      self.hovered                      =                                       event.frame;
+    }
+    self.hoveredOrigin                  =                                       origin ( event );
+    match event.event
+    {
+      EventType::MouseLeftButtonPressed =>
+      {
+        let now: Instant                =                                       event.timestamp;
+        let again: bool
+        = self.lastClick.is_some_and
+          (
+            | ( time, x, y ) |
+                 ( now.saturating_duration_since ( time ) <= self.clickInterval )
+              && ( x.abs_diff ( event.cursorX ) <= self.clickDistance )
+              && ( y.abs_diff ( event.cursorY ) <= self.clickDistance )
+          );
+        //a fourth click starts counting again.
+        self.clickCount
+        = if again && ( self.clickCount < 3 )
+          {
+            self.clickCount + 1
+          }
+          else
+          {
+            1
+          };
+        self.lastClick                  =                                       Some(( now, event.cursorX, event.cursorY ));
+        match self.clickCount
+        {
+          2                             =>                                      listOfGestures.push ( gesture ( event, EventType::DoubleClick, event.frame, origin ( event ) ) ),
+          3                             =>                                      listOfGestures.push ( gesture ( event, EventType::TripleClick, event.frame, origin ( event ) ) ),
+          _                             =>                                      {},
+        }
+        self.pressed                    =                                       Some(( event.frame, event.cursorX, event.cursorY ));
+        self.dragging                   =                                       false;
+      },
+      EventType::MouseMoveWithLeftButton
+                                        =>
+      {
+        if let Some(( source, x, y )) = self.pressed
+        {
+          if !self.dragging
+          && ( ( x.abs_diff ( event.cursorX ) >= self.dragDistance )
+            || ( y.abs_diff ( event.cursorY ) >= self.dragDistance ) )
+          {
+            self.dragging               =                                       true;
+            //dragging is no click, so the next press is a single one again.
+            self.lastClick              =                                       None;
+            listOfGestures.push ( gesture ( event, EventType::DragStart { source: source }, event.frame, origin ( event ) ) );
+          }
+          if self.dragging
+          {
+            listOfGestures.push ( gesture ( event, EventType::DragMove { source: source }, event.frame, origin ( event ) ) );
+          }
+        }
+      },
+      EventType::MouseLeftButtonReleased
+                                        =>
+      {
+        if let Some(( source, _, _ )) = self.pressed.take()
+        {
+          if self.dragging
+          {
+            listOfGestures.push ( gesture ( event, EventType::DragEnd { source: source }, event.frame, origin ( event ) ) );
+          }
+        }
+        self.dragging                   =                                       false;
+      },
+      _                                 =>                                      {},
+    }
+    listOfGestures
+  }
+}
+
+impl Default for GestureRecognizer
+{
+  fn default
+  (
+  ) -> Self
+  {
+    Self::new()
+  }
+}
+
+//where the content of the frame of the event begins on the screen.
+fn origin
+(
+  event:                                &Event,
+) -> ( isize, isize )
+{
+  ( event.cursorX as isize - event.localX, event.cursorY as isize - event.localY )
+}
+
+//a gesture at the position of the event, that it was recognized from, inside the frame beginning at the origin.
+//  drags are reported to the frame under the pointer, the frame they started in is only their source.
+fn gesture
+(
+  event:                                &Event,
+  gesture:                              EventType,
+  frame:                                FrameID,
+  origin:                               ( isize, isize ),
+) -> Event
+{
+  let mut newEvent: Event
+  = Event::new
+    (
+      gesture,
+      event.display,                    frame,
+      event.cursorX,                    event.cursorY,
+      event.mouse,
+    );
+  newEvent.modifiers                    =                                       event.modifiers;
+  newEvent.localX                       =                                       event.cursorX as isize - origin.0;
+  newEvent.localY                       =                                       event.cursorY as isize - origin.1;
+  newEvent.timestamp                    =                                       event.timestamp;
+  newEvent
+}
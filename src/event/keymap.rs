@@ -0,0 +1,410 @@
+use crate::
+{
+  event::
+  {
+    Event,
+    EventType,
+    KeyAction,
+    Modifier,
+  },
+  frame::
+  {
+    FrameID,
+    StatusFrame,
+  },
+};
+
+use std::
+{
+  collections::
+  {
+    HashMap,
+  },
+  fmt,
+  mem,
+};
+
+//keys without the modifiers, that are already part of the key itself.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Debug, Hash)]
+pub enum Key
+{
+  Character(char),
+  Escape,
+  Backspace,
+  Return,
+  Tab,
+  Left,
+  Right,
+  Up,
+  Down,
+  Pause,
+  Insert,
+  Delete,
+  Home,
+  End,
+  PageUp,
+  PageDown,
+  Function(u8),
+}
+
+//a single key together with its modifiers, e.g. Ctrl-x.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Debug, Hash)]
+pub struct KeyStroke
+{
+  pub key:                              Key,
+  pub modifiers:                        Modifier,
+}
+
+impl KeyStroke
+{
+  pub fn new
+  (
+    mut key:                            Key,
+    modifiers:                          Modifier,
+  ) -> Self
+  {
+    //locks are not pressed on purpose.
+    let mut modifiers: Modifier         =                                       modifiers - Modifier::CapsLock - Modifier::NumLock;
+    if let Key::Character(character) = key
+    {
+      //Ctrl-Shift-x arrives as Ctrl-x with shift or as Ctrl-X, so ctrl strokes are lowercase and keep shift,
+      //  while shift is in the character of any other stroke, like Alt-X.
+      if modifiers.contains ( Modifier::Ctrl )
+      {
+        if character.is_uppercase()
+        {
+          modifiers                     |=                                      Modifier::Shift;
+        }
+        key                             =                                       Key::Character(character.to_lowercase().next().unwrap_or ( character ));
+      }
+      else if modifiers.contains ( Modifier::Shift )
+      {
+        modifiers                       -=                                      Modifier::Shift;
+        key                             =                                       Key::Character(character.to_uppercase().next().unwrap_or ( character ));
+      }
+    }
+    Self
+    {
+      key:                              key,
+      modifiers:                        modifiers,
+    }
+  }
+
+  //the key pressed or repeated in this event, releases and anything else are no strokes.
+  pub fn fromEvent
+  (
+    event:                              &Event,
+  ) -> Option<Self>
+  {
+    if event.action == KeyAction::Release
+    {
+      return None;
+    }
+    let ( key, modifiers )
+    = match event.event
+      {
+        EventType::Character(character) =>                                      ( Key::Character(character),                      event.modifiers ),
+        EventType::Ctrl(character)      =>                                      ( Key::Character(character),                      event.modifiers | Modifier::Ctrl ),
+        EventType::Alt(character)       =>                                      ( Key::Character(character),                      event.modifiers | Modifier::Alt  ),
+        EventType::Escape               =>                                      ( Key::Escape,                                    event.modifiers ),
+        EventType::Backspace            =>                                      ( Key::Backspace,                                 event.modifiers ),
+        EventType::Return               =>                                      ( Key::Return,                                    event.modifiers ),
+        EventType::Tab                  =>                                      ( Key::Tab,                                       event.modifiers ),
+        EventType::Left                 =>                                      ( Key::Left,                                      event.modifiers ),
+        EventType::Right                =>                                      ( Key::Right,                                     event.modifiers ),
+        EventType::Up                   =>                                      ( Key::Up,                                        event.modifiers ),
+        EventType::Down                 =>                                      ( Key::Down,                                      event.modifiers ),
+        EventType::Pause                =>                                      ( Key::Pause,                                     event.modifiers ),
+        EventType::Insert               =>                                      ( Key::Insert,                                    event.modifiers ),
+        EventType::Delete               =>                                      ( Key::Delete,                                    event.modifiers ),
+        EventType::Home                 =>                                      ( Key::Home,                                      event.modifiers ),
+        EventType::End                  =>                                      ( Key::End,                                       event.modifiers ),
+        EventType::PageUp               =>                                      ( Key::PageUp,                                    event.modifiers ),
+        EventType::PageDown             =>                                      ( Key::PageDown,                                  event.modifiers ),
+        EventType::Function(number)     =>                                      ( Key::Function(number),                          event.modifiers ),
+        _                               =>                                      return None,
+      };
+    Some(Self::new ( key, modifiers ))
+  }
+
+  //a stroke written like Ctrl-x, Alt-Shift-Up, F5, Space or Minus for the minus key itself.
+  pub fn parse
+  (
+    text:                               &str,
+  ) -> Option<Self>
+  {
+    let mut listOfParts: Vec<&str>      =                                       text.split ( '-' ).collect();
+    let name: &str                      =                                       listOfParts.pop()?;
+    let mut modifiers: Modifier         =                                       Modifier::None;
+    for part                            in                                      listOfParts
+    {
+      modifiers
+      |= match part
+         {
+           "Shift"                      =>                                      Modifier::Shift,
+           "Alt"                        =>                                      Modifier::Alt,
+           "Ctrl"                       =>                                      Modifier::Ctrl,
+           "Super" | "Meta"             =>                                      Modifier::Super,
+           "Hyper"                      =>                                      Modifier::Hyper,
+           _                            =>                                      return None,
+         };
+    }
+    let key: Key
+    = match name
+      {
+        "Escape"                        =>                                      Key::Escape,
+        "Backspace"                     =>                                      Key::Backspace,
+        "Return"                        =>                                      Key::Return,
+        "Tab"                           =>                                      Key::Tab,
+        "Left"                          =>                                      Key::Left,
+        "Right"                         =>                                      Key::Right,
+        "Up"                            =>                                      Key::Up,
+        "Down"                          =>                                      Key::Down,
+        "Pause"                         =>                                      Key::Pause,
+        "Insert"                        =>                                      Key::Insert,
+        "Delete"                        =>                                      Key::Delete,
+        "Home"                          =>                                      Key::Home,
+        "End"                           =>                                      Key::End,
+        "PageUp"                        =>                                      Key::PageUp,
+        "PageDown"                      =>                                      Key::PageDown,
+        "Space"                         =>                                      Key::Character(' '),
+        "Minus"                         =>                                      Key::Character('-'),
+        _                               =>
+        {
+          let mut listOfChars           =                                       name.chars();
+          match ( listOfChars.next(), listOfChars.next() )
+          {
+            ( Some(character), None )   =>                                      Key::Character(character),
+            _                           =>                                      Key::Function(name.strip_prefix ( 'F' )?.parse().ok()?),
+          }
+        },
+      };
+    Some(Self::new ( key, modifiers ))
+  }
+
+  //strokes separated by spaces, e.g. Ctrl-x Ctrl-s.
+  pub fn parseChord
+  (
+    text:                               &str,
+  ) -> Option<Vec<Self>>
+  {
+    let listOfStrokes: Vec<Self>        =                                       text.split_whitespace().map ( Self::parse ).collect::<Option<_>>()?;
+    if listOfStrokes.is_empty()
+    {
+      None
+    }
+    else
+    {
+      Some(listOfStrokes)
+    }
+  }
+}
+
+impl fmt::Display for KeyStroke
+{
+  fn fmt
+  (
+    &self,
+    formatter:                          &mut fmt::Formatter,
+  ) -> fmt::Result
+  {
+    for ( modifier, name )              in                                      [ ( Modifier::Ctrl, "Ctrl-" ), ( Modifier::Alt, "Alt-" ), ( Modifier::Super, "Super-" ), ( Modifier::Hyper, "Hyper-" ), ( Modifier::Shift, "Shift-" ) ]
+    {
+      if self.modifiers.contains ( modifier )
+      {
+        write!( formatter, "{}", name )?;
+      }
+    }
+    match self.key
+    {
+      Key::Character(' ')               =>                                      write!( formatter, "Space" ),
+      Key::Character('-')               =>                                      write!( formatter, "Minus" ),
+      Key::Character(character)         =>                                      write!( formatter, "{}", character ),
+      Key::Function(number)             =>                                      write!( formatter, "F{}", number ),
+      key                               =>                                      write!( formatter, "{:?}", key ),
+    }
+  }
+}
+
+//what a stroke fed to the keymap did.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub enum KeyResult<Action>
+{
+  Action(Action),                                                               // a chord was completed
+  Pending,                                                                      // a chord was begun or continued
+  Cancelled,                                                                    // the stroke does not continue the pending chord, both are dropped
+  Unbound,                                                                      // no stroke or not bound at all, so it is left to the application
+}
+
+//maps chords of strokes to actions of the application.
+//  the bindings of the frame of an event come first, those of FrameID::None apply everywhere else.
+//  a chord stays in the scope, where its first stroke was pressed.
+pub struct Keymap<Action>
+{
+  listOfScopes:                         HashMap<FrameID, Vec<( Vec<KeyStroke>, Action )>>,
+  pending:                              Vec<KeyStroke>,
+  pendingFrame:                         FrameID,
+}
+
+impl<Action: Clone> Keymap<Action>
+{
+  pub fn new
+  (
+  ) -> Self
+  {
+    Self
+    {
+      listOfScopes:                     HashMap::new(),
+      pending:                          vec!(),
+      pendingFrame:                     FrameID::None,
+    }
+  }
+
+  //replaces the action of the chord in this scope, if it was bound already.
+  pub fn bind
+  (
+    &mut self,
+    scope:                              FrameID,
+    chord:                              Vec<KeyStroke>,
+    action:                             Action,
+  ) -> Option<Action>
+  {
+    let listOfBindings                  =                                       self.listOfScopes.entry ( scope ).or_default();
+    if let Some(binding) = listOfBindings.iter_mut().find ( | binding | binding.0 == chord )
+    {
+      return Some(mem::replace ( &mut binding.1, action ));
+    }
+    listOfBindings.push ( ( chord, action ) );
+    None
+  }
+
+  pub fn unbind
+  (
+    &mut self,
+    scope:                              FrameID,
+    chord:                              &[KeyStroke],
+  ) -> Option<Action>
+  {
+    let listOfBindings                  =                                       self.listOfScopes.get_mut ( &scope )?;
+    let index: usize                    =                                       listOfBindings.iter().position ( | binding | binding.0 == chord )?;
+    Some(listOfBindings.remove ( index ).1)
+  }
+
+  //drops all bindings of a frame, e.g. when it is removed.
+  pub fn removeScope
+  (
+    &mut self,
+    scope:                              FrameID,
+  )
+  {
+    self.listOfScopes.remove ( &scope );
+    if self.pendingFrame == scope
+    {
+      self.cancel();
+    }
+  }
+
+  //feeds the stroke of the event into the pending chord.
+  pub fn feed
+  (
+    &mut self,
+    event:                              &Event,
+  ) -> KeyResult<Action>
+  {
+    let stroke: KeyStroke
+    = match KeyStroke::fromEvent ( event )
+      {
+        Some(stroke)                    =>                                      stroke,
+        None                            =>                                      return KeyResult::Unbound,
+      };
+    if self.pending.is_empty()
+    {
+      self.pendingFrame                 =                                       event.frame;
+    }
+    self.pending.push ( stroke );
+    for scope                           in                                      [ self.pendingFrame, FrameID::None ]
+    {
+      let mut prefix: bool              =                                       false;
+      for ( chord, action )             in                                      self.listOfScopes.get ( &scope ).into_iter().flatten()
+      {
+        if *chord == self.pending
+        {
+          let action: Action            =                                       action.clone();
+          self.pending.clear();
+          return KeyResult::Action(action);
+        }
+        prefix                          |=                                      chord.starts_with ( &self.pending );
+      }
+      if prefix
+      {
+        return KeyResult::Pending;
+      }
+    }
+    let chord: bool                     =                                       self.pending.len() > 1;
+    self.pending.clear();
+    if chord
+    {
+      KeyResult::Cancelled
+    }
+    else
+    {
+      KeyResult::Unbound
+    }
+  }
+
+  pub fn cancel
+  (
+    &mut self,
+  )
+  {
+    self.pending.clear();
+  }
+
+  //the strokes of the chord begun so far.
+  pub fn pending
+  (
+    &self,
+  ) -> &[KeyStroke]
+  {
+    &self.pending
+  }
+
+  //the pending chord as written for parseChord(), e.g. "Ctrl-x ", or nothing.
+  pub fn pendingText
+  (
+    &self,
+  ) -> String
+  {
+    let mut text: String                =                                       String::new();
+    for stroke                          in                                      self.pending.iter()
+    {
+      text.push_str ( &format!( "{} ", stroke ) );
+    }
+    text
+  }
+
+  //puts the pending chord into the status frame, which is only redrawn, if it changed.
+  pub fn showPending
+  (
+    &self,
+    status:                             &mut StatusFrame,
+  )
+  {
+    let text: String                    =                                       self.pendingText();
+    if status.text != text
+    {
+      status.text                       =                                       text;
+      status.changed                    =                                       true;
+    }
+  }
+}
+
+impl<Action: Clone> Default for Keymap<Action>
+{
+  fn default
+  (
+  ) -> Self
+  {
+    Self::new()
+  }
+}
@@ -1,5 +1,10 @@
+pub mod gesture;
+pub mod keymap;
+pub mod record;
+
 use crate::
 {
+  Error,
   Flags,
   display::
   {
@@ -22,6 +27,10 @@ use std::
       Sender,
     },
   },
+  time::
+  {
+    Instant,
+  },
 };
 
 pub type EventReceiver                  =                                       Receiver<Event>;
@@ -35,18 +44,49 @@ bitflags!
     const LeftDown                      =                                       0b0000_0000_0000_0000_0000_0000_0000_0001;
     const MiddleDown                    =                                       0b0000_0000_0000_0000_0000_0000_0000_0010;
     const RightDown                     =                                       0b0000_0000_0000_0000_0000_0000_0000_0100;
+    const BackDown                      =                                       0b0000_0000_0000_0000_0000_0000_0000_1000;
+    const ForwardDown                   =                                       0b0000_0000_0000_0000_0000_0000_0001_0000;
   }
 }
 
+//as encoded by xterm: the modifier parameter of a key is one plus these bits.
+bitflags!
+{
+  pub struct Modifier: Flags
+  {
+    const None                          =                                       0b0000_0000_0000_0000_0000_0000_0000_0000;
+    const Shift                         =                                       0b0000_0000_0000_0000_0000_0000_0000_0001;
+    const Alt                           =                                       0b0000_0000_0000_0000_0000_0000_0000_0010;
+    const Ctrl                          =                                       0b0000_0000_0000_0000_0000_0000_0000_0100;
+    const Meta                          =                                       0b0000_0000_0000_0000_0000_0000_0000_1000;
+    //the kitty keyboard protocol calls the fourth bit super and reports some more.
+    const Super                         =                                       0b0000_0000_0000_0000_0000_0000_0000_1000;
+    const Hyper                         =                                       0b0000_0000_0000_0000_0000_0000_0001_0000;
+    const KittyMeta                     =                                       0b0000_0000_0000_0000_0000_0000_0010_0000;
+    const CapsLock                      =                                       0b0000_0000_0000_0000_0000_0000_0100_0000;
+    const NumLock                       =                                       0b0000_0000_0000_0000_0000_0000_1000_0000;
+  }
+}
+
+//only terminals speaking the kitty keyboard protocol report anything but presses.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Debug)]
+pub enum KeyAction
+{
+  Press,
+  Repeat,
+  Release,
+}
+
 pub enum EventType
 {
-  Error(&'static str),
+  Error(Error),
   Warning(&'static str),
 
   Character(char),
   Escape,
   Backspace,
   Return,
+  Tab,
   Left,
   Right,
   Up,
@@ -61,20 +101,61 @@ pub enum EventType
   Function(u8),
   Alt(char),
   Ctrl(char),
+  Paste(String),
   
   MouseOver,
   MouseLeftButtonPressed,
   MouseMiddleButtonPressed,
   MouseRightButtonPressed,
+  MouseBackButtonPressed,
+  MouseForwardButtonPressed,
   MouseLeftButtonReleased,
   MouseMiddleButtonReleased,
   MouseRightButtonReleased,
+  MouseBackButtonReleased,
+  MouseForwardButtonReleased,
   MouseWheelUp,
   MouseWheelDown,
+  MouseWheelLeft,
+  MouseWheelRight,
   MouseMoveWithLeftButton,
   MouseMoveWithMiddleButton,
   MouseMoveWithRightButton,
+  MouseMoveWithBackButton,
+  MouseMoveWithForwardButton,
+  //synthesized by the gesture recognizer, the frame of the event is the one under the pointer.
+  MouseEnter,
+  MouseLeave,
+  DoubleClick,
+  TripleClick,
+  DragStart
+  {
+    source:                             FrameID,                                // where the left button was pressed
+  },
+  DragMove
+  {
+    source:                             FrameID,
+  },
+  DragEnd
+  {
+    source:                             FrameID,
+  },
   CursorPosition,
+  KeyboardProtocol(Flags),                                                      // flags of the kitty keyboard protocol currently enabled
+  DeviceAttributes,
+  Resize
+  {
+    width:                              usize,
+    height:                             usize,
+  },
+  Suspended,
+  Resumed,
+  //the terminal window, not a frame.
+  DisplayFocusGained(DisplayID),
+  DisplayFocusLost(DisplayID),
+  //the frame of the event got or lost the focus of its display.
+  FrameFocusGained,
+  FrameFocusLost,
 }
 
 pub struct Event
@@ -84,7 +165,13 @@ pub struct Event
   pub frame:                            FrameID,
   pub cursorX:                          usize,
   pub cursorY:                          usize,
+  pub localX:                           isize,                                  // position of the mouse inside the content of the frame
+  pub localY:                           isize,
   pub mouse:                            MouseButton,
+  pub modifiers:                        Modifier,
+  pub action:                           KeyAction,
+  pub timestamp:                        Instant,                                // when the input was decoded, or the event created otherwise
+  pub sequence:                         u64,                                    // order, in which waitEvent() delivered the events, zero until then
 }
 
 impl Event
@@ -106,9 +193,36 @@ impl Event
       frame:                            frame,
       cursorX:                          cursorX,
       cursorY:                          cursorY,
+      localX:                           0,
+      localY:                           0,
       mouse:                            mouse,
+      modifiers:                        Modifier::None,
+      action:                           KeyAction::Press,
+      timestamp:                        Instant::now(),
+      sequence:                         0,
     }
   }
+
+  pub fn isMouse
+  (
+    &self,
+  ) -> bool
+  {
+    matches!
+    (
+      self.event,
+      EventType::MouseOver
+      | EventType::MouseLeftButtonPressed     | EventType::MouseMiddleButtonPressed   | EventType::MouseRightButtonPressed
+      | EventType::MouseBackButtonPressed     | EventType::MouseForwardButtonPressed
+      | EventType::MouseLeftButtonReleased    | EventType::MouseMiddleButtonReleased  | EventType::MouseRightButtonReleased
+      | EventType::MouseBackButtonReleased    | EventType::MouseForwardButtonReleased
+      | EventType::MouseWheelUp               | EventType::MouseWheelDown
+      | EventType::MouseWheelLeft             | EventType::MouseWheelRight
+      | EventType::MouseMoveWithLeftButton    | EventType::MouseMoveWithMiddleButton  | EventType::MouseMoveWithRightButton
+      | EventType::MouseMoveWithBackButton    | EventType::MouseMoveWithForwardButton
+    )
+  }
+
   pub fn openChannel
   (
   ) -> ( EventSender, EventReceiver )
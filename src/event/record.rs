@@ -0,0 +1,431 @@
+use crate::
+{
+  display::
+  {
+    DisplayID,
+  },
+  event::
+  {
+    Event,
+    EventSender,
+    EventType,
+    KeyAction,
+    Modifier,
+    MouseButton,
+  },
+  frame::
+  {
+    FrameID,
+  },
+  uid::
+  {
+    UID,
+  },
+};
+
+use std::
+{
+  fs::
+  {
+    File,
+  },
+  io::
+  {
+    self,
+    BufRead,
+    BufReader,
+    BufWriter,
+    Write,
+  },
+  path::
+  {
+    Path,
+  },
+  thread::
+  {
+    self,
+    JoinHandle,
+  },
+  time::
+  {
+    Duration,
+    Instant,
+  },
+};
+
+//writes events line by line with their timestamps relative to the start of the recording, e.g.
+//  1500 1.1 2.1 4 2 4 2 1 0 Press MouseLeftButtonPressed
+//  that is microseconds, display, frame, position on the screen, inside the frame, mouse buttons, modifiers, action and the event itself.
+//  only input is recorded: errors, warnings, focus moves, resizes, suspends and answers of the terminal are results of the session,
+//  that a replay brings about again by itself.
+pub struct EventRecorder
+{
+  output:                               Box<dyn Write + Send>,
+  start:                                Instant,
+}
+
+impl EventRecorder
+{
+  pub fn new
+  (
+    output:                             Box<dyn Write + Send>,
+  ) -> Self
+  {
+    Self
+    {
+      output:                           output,
+      start:                            Instant::now(),
+    }
+  }
+
+  pub fn create
+  (
+    path:                               impl AsRef<Path>,
+  ) -> io::Result<Self>
+  {
+    Ok(Self::new ( Box::new ( BufWriter::new ( File::create ( path )? ) ) ))
+  }
+
+  //every event is flushed right away, so a crash does not lose the events, that led to it.
+  pub fn record
+  (
+    &mut self,
+    event:                              &Event,
+  ) -> io::Result<()>
+  {
+    if let Some(name) = encodeEvent ( &event.event )
+    {
+      writeln!
+      (
+        self.output,
+        "{} {} {} {} {} {} {} {} {} {:?} {}",
+        event.timestamp.saturating_duration_since ( self.start ).as_micros(),
+        event.display,                  event.frame,
+        event.cursorX,                  event.cursorY,
+        event.localX,                   event.localY,
+        event.mouse.bits(),             event.modifiers.bits(),
+        event.action,                   name,
+      )?;
+      self.output.flush()?;
+    }
+    Ok(())
+  }
+}
+
+//events read from a recording, that can be sent again in the same order and with the same pauses in between.
+//  the UIDs are kept as they are, so the displays and frames have to be added in the same order as before.
+pub struct EventReplay
+{
+  listOfEvents:                         Vec<( Duration, Event )>,
+}
+
+impl EventReplay
+{
+  pub fn read
+  (
+    input:                              impl BufRead,
+  ) -> io::Result<Self>
+  {
+    let mut listOfEvents                =                                       vec!();
+    for ( number, line )                in                                      input.lines().enumerate()
+    {
+      let line: String                  =                                       line?;
+      if line.trim().is_empty()
+      {
+        continue;
+      }
+      match decodeLine ( &line )
+      {
+        Some(record)                    =>                                      listOfEvents.push ( record ),
+        None                            =>                                      return Err(io::Error::new ( io::ErrorKind::InvalidData, format!( "invalid event in line {}", number + 1 ) )),
+      }
+    }
+    Ok(Self { listOfEvents: listOfEvents })
+  }
+
+  pub fn open
+  (
+    path:                               impl AsRef<Path>,
+  ) -> io::Result<Self>
+  {
+    Self::read ( BufReader::new ( File::open ( path )? ) )
+  }
+
+  pub fn len
+  (
+    &self,
+  ) -> usize
+  {
+    self.listOfEvents.len()
+  }
+
+  pub fn is_empty
+  (
+    &self,
+  ) -> bool
+  {
+    self.listOfEvents.is_empty()
+  }
+
+  //sends the events, speed 2.0 is twice as fast as recorded, zero or infinity does not pause at all.
+  //  stops early, if nobody receives the events anymore.
+  pub fn replay
+  (
+    self,
+    sender:                             &EventSender,
+    speed:                              f64,
+  )
+  {
+    let start: Instant                  =                                       Instant::now();
+    for ( time, mut event )             in                                      self.listOfEvents
+    {
+      if speed.is_finite()
+      && speed > 0.0
+      {
+        thread::sleep ( ( start + time.div_f64 ( speed ) ).saturating_duration_since ( Instant::now() ) );
+      }
+      //the replayed input happens now, and is numbered by whoever receives it.
+      event.timestamp                   =                                       Instant::now();
+      if sender.send ( event ).is_err()
+      {
+        break;
+      }
+    }
+  }
+
+  //replays in the background, e.g. into the sendChannel of a Ferrocene.
+  pub fn spawn
+  (
+    self,
+    sender:                             EventSender,
+    speed:                              f64,
+  ) -> JoinHandle<()>
+  {
+    thread::spawn
+    (
+      move ||
+      {
+        self.replay ( &sender, speed );
+      }
+    )
+  }
+}
+
+//the name of the event followed by its arguments, characters as code points and pasted text as hex, so nothing contains spaces.
+fn encodeEvent
+(
+  event:                                &EventType,
+) -> Option<String>
+{
+  let name: &str
+  = match event
+    {
+      EventType::Error(_)
+      | EventType::Warning(_)
+      | EventType::FrameFocusGained
+      | EventType::FrameFocusLost
+      | EventType::Resize { .. }
+      | EventType::Suspended
+      | EventType::Resumed
+      | EventType::CursorPosition
+      | EventType::KeyboardProtocol(_)
+      | EventType::DeviceAttributes     =>                                      return None,
+      EventType::Character(character)   =>                                      return Some(format!( "Character {}", *character as u32 )),
+      EventType::Alt(character)         =>                                      return Some(format!( "Alt {}",       *character as u32 )),
+      EventType::Ctrl(character)        =>                                      return Some(format!( "Ctrl {}",      *character as u32 )),
+      EventType::Function(number)       =>                                      return Some(format!( "Function {}",  number )),
+      EventType::Paste(text)            =>
+      {
+        let mut hex: String             =                                       String::from ( "x" );
+        for byte                        in                                      text.bytes()
+        {
+          hex.push_str ( &format!( "{:02x}", byte ) );
+        }
+        return Some(format!( "Paste {}", hex ));
+      },
+      EventType::DragStart { source }   =>                                      return Some(format!( "DragStart {}", source )),
+      EventType::DragMove  { source }   =>                                      return Some(format!( "DragMove {}",  source )),
+      EventType::DragEnd   { source }   =>                                      return Some(format!( "DragEnd {}",   source )),
+      EventType::DisplayFocusGained(display)
+                                        =>                                      return Some(format!( "DisplayFocusGained {}", display )),
+      EventType::DisplayFocusLost(display)
+                                        =>                                      return Some(format!( "DisplayFocusLost {}",   display )),
+      EventType::Escape                 =>                                      "Escape",
+      EventType::Backspace              =>                                      "Backspace",
+      EventType::Return                 =>                                      "Return",
+      EventType::Tab                    =>                                      "Tab",
+      EventType::Left                   =>                                      "Left",
+      EventType::Right                  =>                                      "Right",
+      EventType::Up                     =>                                      "Up",
+      EventType::Down                   =>                                      "Down",
+      EventType::Pause                  =>                                      "Pause",
+      EventType::Insert                 =>                                      "Insert",
+      EventType::Delete                 =>                                      "Delete",
+      EventType::Home                   =>                                      "Home",
+      EventType::End                    =>                                      "End",
+      EventType::PageUp                 =>                                      "PageUp",
+      EventType::PageDown               =>                                      "PageDown",
+      EventType::MouseOver              =>                                      "MouseOver",
+      EventType::MouseLeftButtonPressed =>                                      "MouseLeftButtonPressed",
+      EventType::MouseMiddleButtonPressed
+                                        =>                                      "MouseMiddleButtonPressed",
+      EventType::MouseRightButtonPressed
+                                        =>                                      "MouseRightButtonPressed",
+      EventType::MouseBackButtonPressed =>                                      "MouseBackButtonPressed",
+      EventType::MouseForwardButtonPressed
+                                        =>                                      "MouseForwardButtonPressed",
+      EventType::MouseLeftButtonReleased
+                                        =>                                      "MouseLeftButtonReleased",
+      EventType::MouseMiddleButtonReleased
+                                        =>                                      "MouseMiddleButtonReleased",
+      EventType::MouseRightButtonReleased
+                                        =>                                      "MouseRightButtonReleased",
+      EventType::MouseBackButtonReleased
+                                        =>                                      "MouseBackButtonReleased",
+      EventType::MouseForwardButtonReleased
+                                        =>                                      "MouseForwardButtonReleased",
+      EventType::MouseWheelUp           =>                                      "MouseWheelUp",
+      EventType::MouseWheelDown         =>                                      "MouseWheelDown",
+      EventType::MouseWheelLeft         =>                                      "MouseWheelLeft",
+      EventType::MouseWheelRight        =>                                      "MouseWheelRight",
+      EventType::MouseMoveWithLeftButton
+                                        =>                                      "MouseMoveWithLeftButton",
+      EventType::MouseMoveWithMiddleButton
+                                        =>                                      "MouseMoveWithMiddleButton",
+      EventType::MouseMoveWithRightButton
+                                        =>                                      "MouseMoveWithRightButton",
+      EventType::MouseMoveWithBackButton
+                                        =>                                      "MouseMoveWithBackButton",
+      EventType::MouseMoveWithForwardButton
+                                        =>                                      "MouseMoveWithForwardButton",
+      EventType::MouseEnter             =>                                      "MouseEnter",
+      EventType::MouseLeave             =>                                      "MouseLeave",
+      EventType::DoubleClick            =>                                      "DoubleClick",
+      EventType::TripleClick            =>                                      "TripleClick",
+    };
+  Some(name.to_string())
+}
+
+fn decodeEvent<'a>
+(
+  name:                                 &str,
+  listOfArguments:                      &mut impl Iterator<Item = &'a str>,
+) -> Option<EventType>
+{
+  let event: EventType
+  = match name
+    {
+      "Character"                       =>                                      EventType::Character(char::from_u32 ( listOfArguments.next()?.parse().ok()? )?),
+      "Alt"                             =>                                      EventType::Alt(char::from_u32 ( listOfArguments.next()?.parse().ok()? )?),
+      "Ctrl"                            =>                                      EventType::Ctrl(char::from_u32 ( listOfArguments.next()?.parse().ok()? )?),
+      "Function"                        =>                                      EventType::Function(listOfArguments.next()?.parse().ok()?),
+      "Paste"                           =>
+      {
+        let hex: &str                   =                                       listOfArguments.next()?.strip_prefix ( 'x' )?;
+        if !hex.is_ascii()
+        || !hex.len().is_multiple_of ( 2 )
+        {
+          return None;
+        }
+        let mut listOfBytes: Vec<u8>    =                                       vec!();
+        for index                       in                                      ( 0 .. hex.len() ).step_by ( 2 )
+        {
+          listOfBytes.push ( u8::from_str_radix ( &hex [ index .. index + 2 ], 16 ).ok()? );
+        }
+        EventType::Paste(String::from_utf8 ( listOfBytes ).ok()?)
+      },
+      "DragStart"                       =>                                      EventType::DragStart { source: decodeUID ( listOfArguments.next()? )? },
+      "DragMove"                        =>                                      EventType::DragMove  { source: decodeUID ( listOfArguments.next()? )? },
+      "DragEnd"                         =>                                      EventType::DragEnd   { source: decodeUID ( listOfArguments.next()? )? },
+      "DisplayFocusGained"              =>                                      EventType::DisplayFocusGained(decodeUID ( listOfArguments.next()? )?),
+      "DisplayFocusLost"                =>                                      EventType::DisplayFocusLost(decodeUID ( listOfArguments.next()? )?),
+      "Escape"                          =>                                      EventType::Escape,
+      "Backspace"                       =>                                      EventType::Backspace,
+      "Return"                          =>                                      EventType::Return,
+      "Tab"                             =>                                      EventType::Tab,
+      "Left"                            =>                                      EventType::Left,
+      "Right"                           =>                                      EventType::Right,
+      "Up"                              =>                                      EventType::Up,
+      "Down"                            =>                                      EventType::Down,
+      "Pause"                           =>                                      EventType::Pause,
+      "Insert"                          =>                                      EventType::Insert,
+      "Delete"                          =>                                      EventType::Delete,
+      "Home"                            =>                                      EventType::Home,
+      "End"                             =>                                      EventType::End,
+      "PageUp"                          =>                                      EventType::PageUp,
+      "PageDown"                        =>                                      EventType::PageDown,
+      "MouseOver"                       =>                                      EventType::MouseOver,
+      "MouseLeftButtonPressed"          =>                                      EventType::MouseLeftButtonPressed,
+      "MouseMiddleButtonPressed"        =>                                      EventType::MouseMiddleButtonPressed,
+      "MouseRightButtonPressed"         =>                                      EventType::MouseRightButtonPressed,
+      "MouseBackButtonPressed"          =>                                      EventType::MouseBackButtonPressed,
+      "MouseForwardButtonPressed"       =>                                      EventType::MouseForwardButtonPressed,
+      "MouseLeftButtonReleased"         =>                                      EventType::MouseLeftButtonReleased,
+      "MouseMiddleButtonReleased"       =>                                      EventType::MouseMiddleButtonReleased,
+      "MouseRightButtonReleased"        =>                                      EventType::MouseRightButtonReleased,
+      "MouseBackButtonReleased"         =>                                      EventType::MouseBackButtonReleased,
+      "MouseForwardButtonReleased"      =>                                      EventType::MouseForwardButtonReleased,
+      "MouseWheelUp"                    =>                                      EventType::MouseWheelUp,
+      "MouseWheelDown"                  =>                                      EventType::MouseWheelDown,
+      "MouseWheelLeft"                  =>                                      EventType::MouseWheelLeft,
+      "MouseWheelRight"                 =>                                      EventType::MouseWheelRight,
+      "MouseMoveWithLeftButton"         =>                                      EventType::MouseMoveWithLeftButton,
+      "MouseMoveWithMiddleButton"       =>                                      EventType::MouseMoveWithMiddleButton,
+      "MouseMoveWithRightButton"        =>                                      EventType::MouseMoveWithRightButton,
+      "MouseMoveWithBackButton"         =>                                      EventType::MouseMoveWithBackButton,
+      "MouseMoveWithForwardButton"      =>                                      EventType::MouseMoveWithForwardButton,
+      "MouseEnter"                      =>                                      EventType::MouseEnter,
+      "MouseLeave"                      =>                                      EventType::MouseLeave,
+      "DoubleClick"                     =>                                      EventType::DoubleClick,
+      "TripleClick"                     =>                                      EventType::TripleClick,
+      _                                 =>                                      return None,
+    };
+  Some(event)
+}
+
+//index.generation as written by Display.
+fn decodeUID
+(
+  text:                                 &str,
+) -> Option<UID>
+{
+  let ( index, generation )             =                                       text.split_once ( '.' )?;
+  Some(UID::new ( index.parse().ok()?, generation.parse().ok()? ))
+}
+
+fn decodeLine
+(
+  line:                                 &str,
+) -> Option<( Duration, Event )>
+{
+  let mut listOfFields                  =                                       line.split_whitespace();
+  let time: Duration                    =                                       Duration::from_micros ( listOfFields.next()?.parse().ok()? );
+  let display: DisplayID                =                                       decodeUID ( listOfFields.next()? )?;
+  let frame: FrameID                    =                                       decodeUID ( listOfFields.next()? )?;
+  let cursorX: usize                    =                                       listOfFields.next()?.parse().ok()?;
+  let cursorY: usize                    =                                       listOfFields.next()?.parse().ok()?;
+  let localX: isize                     =                                       listOfFields.next()?.parse().ok()?;
+  let localY: isize                     =                                       listOfFields.next()?.parse().ok()?;
+  let mouse: MouseButton                =                                       MouseButton::from_bits ( listOfFields.next()?.parse().ok()? )?;
+  let modifiers: Modifier               =                                       Modifier::from_bits ( listOfFields.next()?.parse().ok()? )?;
+  let action: KeyAction
+  = match listOfFields.next()?
+    {
+      "Press"                           =>                                      KeyAction::Press,
+      "Repeat"                          =>                                      KeyAction::Repeat,
+      "Release"                         =>                                      KeyAction::Release,
+      _                                 =>                                      return None,
+    };
+  let name: &str                        =                                       listOfFields.next()?;
+  let event: EventType                  =                                       decodeEvent ( name, &mut listOfFields )?;
+  if listOfFields.next().is_some()
+  {
+    return None;
+  }
+  let mut newEvent: Event               =                                       Event::new ( event, display, frame, cursorX, cursorY, mouse );
+  newEvent.localX                       =                                       localX;
+  newEvent.localY                       =                                       localY;
+  newEvent.modifiers                    =                                       modifiers;
+  newEvent.action                       =                                       action;
+  Some(( time, newEvent ))
+}
@@ -3,6 +3,10 @@ pub mod style;
 use crate::
 {
   Flags,
+  uid::
+  {
+    UID,
+  },
   frame::
   {
     style::
@@ -13,7 +17,7 @@ use crate::
   },
 };
 
-pub type FrameID                        =                                       usize;
+pub type FrameID                        =                                       UID;
 type GridBorder                         =                                       usize;
 
 bitflags!
@@ -30,6 +34,7 @@ pub struct StatusFrame
   pub offs:                             isize,
   pub text:                             String,
   pub bgChar:                           char,
+  pub changed:                          bool,
 }
 
 pub struct TextFrame
@@ -39,6 +44,7 @@ pub struct TextFrame
   pub offsY:                            isize,
   pub lines:                            Vec<String>,
   pub bgChar:                           char,
+  pub changed:                          bool,
 }
 
 pub struct EditorFrame
@@ -48,6 +54,7 @@ pub struct EditorFrame
   pub offsY:                            isize,
   pub lines:                            Vec<Vec<StyledToken>>,
   pub bgChar:                           char,
+  pub changed:                          bool,
 }
 
 pub enum PixelEncoding
@@ -73,6 +80,7 @@ pub struct PixelFrame
 
 pub struct PlotFrame
 {
+  pub changed:                          bool,
 }
 
 pub enum Tiling
@@ -107,11 +115,13 @@ pub struct ParentFrame
   pub gridMinimumX:                     Vec<usize>,
   pub gridMinimumY:                     Vec<usize>,
   pub pivotFrame:                       FrameID,
+  pub changed:                          bool,
 }
 
 pub struct LayerFrame
 {
   pub listOfLayers:                     Vec<FrameID>,
+  pub changed:                          bool,
 }
 
 pub enum Frame
@@ -127,6 +137,64 @@ pub enum Frame
 
 impl Frame
 {
+  pub fn isChanged
+  (
+    &self,
+  ) -> bool
+  {
+    match self
+    {
+      Frame::Status ( ref frame )       =>                                      frame.changed,
+      Frame::Text   ( ref frame )       =>                                      frame.changed,
+      Frame::Editor ( ref frame )       =>                                      frame.changed,
+      Frame::Pixel  ( ref frame )       =>                                      frame.changed,
+      Frame::Plot   ( ref frame )       =>                                      frame.changed,
+      Frame::Parent ( ref frame )       =>                                      frame.changed,
+      Frame::Layers ( ref frame )       =>                                      frame.changed,
+    }
+  }
+
+  pub fn setChanged
+  (
+    &mut self,
+    changed:                            bool,
+  )
+  {
+    match self
+    {
+      Frame::Status ( ref mut frame )   =>                                      frame.changed = changed,
+      Frame::Text   ( ref mut frame )   =>                                      frame.changed = changed,
+      Frame::Editor ( ref mut frame )   =>                                      frame.changed = changed,
+      Frame::Pixel  ( ref mut frame )   =>                                      frame.changed = changed,
+      Frame::Plot   ( ref mut frame )   =>                                      frame.changed = changed,
+      Frame::Parent ( ref mut frame )   =>                                      frame.changed = changed,
+      Frame::Layers ( ref mut frame )   =>                                      frame.changed = changed,
+    }
+  }
+
+  //how far the content is scrolled, frames without offsets are never.
+  pub fn scrollOffset
+  (
+    &self,
+  ) -> ( isize, isize )
+  {
+    match self
+    {
+      Frame::Text   ( ref frame )       =>                                      ( frame.offsX, frame.offsY ),
+      Frame::Editor ( ref frame )       =>                                      ( frame.offsX, frame.offsY ),
+      _                                 =>                                      ( 0, 0 ),
+    }
+  }
+
+  //frames without subframes
+  pub fn isLeaf
+  (
+    &self,
+  ) -> bool
+  {
+    !matches!( self, Frame::Parent ( _ ) | Frame::Layers ( _ ) )
+  }
+
   pub fn newStatusFrame
   (
     flags:                              FrameFlag,
@@ -143,6 +211,7 @@ impl Frame
         offs:                           offs,
         text:                           text,
         bgChar:                         bgChar,
+        changed:                        true,
       }
     )
   }
@@ -165,6 +234,7 @@ impl Frame
         offsY:                          offsY,
         lines:                          lines,
         bgChar:                         bgChar,
+        changed:                        true,
       }
     )
   }
@@ -187,6 +257,7 @@ impl Frame
         offsY:                          offsY,
         lines:                          lines,
         bgChar:                         bgChar,
+        changed:                        true,
       }
     )
   }
@@ -281,7 +352,23 @@ impl Frame
         gridMinimumX:                   gridMinimumX,
         gridMinimumY:                   gridMinimumY,
         pivotFrame:                     pivotFrame,
+        changed:                        true,
       }
     )
   }
-}
\ No newline at end of file
+
+  pub fn newLayerFrame
+  (
+    listOfLayers:                       Vec<FrameID>,
+  ) -> Frame
+  {
+    Frame::Layers
+    (
+      LayerFrame
+      {
+        listOfLayers:                   listOfLayers,
+        changed:                        true,
+      }
+    )
+  }
+}
@@ -23,6 +23,7 @@ bitflags!
   }
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Colour
 {
   Default,
@@ -5,17 +5,43 @@
 extern crate bitflags;
 
 pub mod display;
+pub mod error;
 pub mod frame;
 pub mod event;
+pub mod uid;
 
 pub use crate::
 {
+  error::
+  {
+    Error,
+  },
+  uid::
+  {
+    Slots,
+    UID,
+  },
   display::
   {
     Display,
+    memory::
+    {
+      MemoryDisplay,
+    },
+    cell::
+    {
+      Cell,
+      CellBuffer,
+    },
+    input::
+    {
+      InputParser,
+    },
     DisplayFlag,
     DisplayID,
     DisplayType,
+    FrameMap,
+    MappedFrame,
     ReadableFd,
     WriteableFd,
   },
@@ -26,6 +52,22 @@ pub use crate::
     EventSender,
     EventType,
     MouseButton,
+    gesture::
+    {
+      GestureRecognizer,
+    },
+    keymap::
+    {
+      Key,
+      KeyResult,
+      KeyStroke,
+      Keymap,
+    },
+    record::
+    {
+      EventRecorder,
+      EventReplay,
+    },
   },
   frame::
   {
@@ -55,9 +97,23 @@ use crate::display::tty::*;
 
 use std::
 {
+  collections::
+  {
+    VecDeque,
+  },
+  io,
+  sync::
+  {
+    mpsc::
+    {
+      RecvTimeoutError,
+    },
+  },
   time::
   {
-    SystemTime
+    Duration,
+    Instant,
+    SystemTime,
   },
 };
 
@@ -65,10 +121,14 @@ type Flags                              =
 
 pub struct Ferrocene
 {
-  pub listOfDisplays:                   Vec<Option<Display>>,
-  pub listOfFrames:                     Vec<Option<Frame>>,
+  pub listOfDisplays:                   Slots<Display>,
+  pub listOfFrames:                     Slots<Frame>,
   pub recvChannel:                      EventReceiver,
   pub sendChannel:                      EventSender,
+  pub gestures:                         Option<GestureRecognizer>,              // synthesizes clicks, drags and hovering, if set
+  pub recorder:                         Option<EventRecorder>,                  // writes every received event, if set
+  pendingEvents:                        VecDeque<Event>,                        // recognized, but not returned yet
+  eventCounter:                         u64,                                    // sequence number of the event delivered last
 }
 
 impl Ferrocene
@@ -80,22 +140,28 @@ impl Ferrocene
     let ( sendChannel, recvChannel )    =                                       event::Event::openChannel();
     Self
     {
-      listOfDisplays:                   vec!(),
-      listOfFrames:                     vec!(),
+      listOfDisplays:                   Slots::new(),
+      listOfFrames:                     Slots::new(),
       recvChannel:                      recvChannel,
       sendChannel:                      sendChannel,
+      gestures:                         None,
+      recorder:                         None,
+      pendingEvents:                    VecDeque::new(),
+      eventCounter:                     0,
     }
   }
 
   pub fn addDisplay
   (
     &mut self,
-    mut display:                        Display,
+    display:                            Display,
   ) -> DisplayID
   {
-    let id                              =                                       self.listOfDisplays.len() + 1;
-    display.this                        =                                       id;
-    self.listOfDisplays.push(Some(display));
+    let id                              =                                       self.listOfDisplays.insert(display);
+    if let Some(display) = self.listOfDisplays.access(id)
+    {
+      display.this                      =                                       id;
+    }
     id
   }
 
@@ -105,8 +171,73 @@ impl Ferrocene
     frame:                              Frame,
   ) -> FrameID
   {
-    self.listOfFrames.push(Some(frame));
-    self.listOfFrames.len()
+    self.listOfFrames.insert(frame)
+  }
+
+  //turns the display off, if necessary, and frees its slot.
+  pub fn removeDisplay
+  (
+    &mut self,
+    display:                            DisplayID,
+  ) -> Result<Display, Error>
+  {
+    let events                          =                                       self.sendChannel.clone();
+    let refDisplay                      =                                       self.accessDisplay ( display )?;
+    if ( refDisplay.flags & DisplayFlag::TurnedOn ) != DisplayFlag::None
+    {
+      refDisplay.flags                  &=                                      !DisplayFlag::TurnedOn;
+      refDisplay.turnOff(events);
+    }
+    self.listOfDisplays.remove ( display ).ok_or ( Error::InvalidatedDisplay ( display ) )
+  }
+
+  //frees the slot of the frame, all UIDs of it become stale and are ignored when drawing.
+  pub fn removeFrame
+  (
+    &mut self,
+    frame:                              FrameID,
+  ) -> Result<Frame, Error>
+  {
+    if !self.listOfFrames.isKnown ( frame )
+    {
+      Err(Error::InvalidFrame(frame))
+    }
+    else if let Some(removed) = self.listOfFrames.remove ( frame )
+    {
+      //whatever was below the removed frame has to be drawn again.
+      for display                       in                                      self.listOfDisplays.iter_mut()
+      {
+        display.flags                   |=                                      DisplayFlag::MaskRefresh;
+      }
+      Ok(removed)
+    }
+    else
+    {
+      Err(Error::InvalidatedFrame(frame))
+    }
+  }
+
+  pub fn addMemoryDisplay
+  (
+    &mut self,
+    flags:                              DisplayFlag,
+    offsX:                              isize,
+    offsY:                              isize,
+    sizeX:                              usize,
+    sizeY:                              usize,
+    refreshRate:                        u64,
+  ) -> DisplayID
+  {
+    self.addDisplay
+    (
+      MemoryDisplay::new
+      (
+        flags,
+        offsX,                          offsY,
+        sizeX,                          sizeY,
+        refreshRate,
+      )
+    )
   }
 
   #[cfg(feature = "display-tty")]
@@ -121,7 +252,7 @@ impl Ferrocene
     input:                              Box<display::ReadableFd>,
     output:                             Box<display::WriteableFd>,
     refreshRate:                        u64,
-  ) -> ( usize, usize, DisplayID )
+  ) -> Result<( usize, usize, DisplayID ), Error>
   {
     let display
     = TTYDisplay::new
@@ -131,11 +262,14 @@ impl Ferrocene
         cursorX,                        cursorY,
         input,                          output,
         refreshRate,
-      ).unwrap();
+      )?;
+    Ok
     (
-      display.sizeX,
-      display.sizeY,
-      self.addDisplay(display)
+      (
+        display.sizeX,
+        display.sizeY,
+        self.addDisplay(display)
+      )
     )
   }
 
@@ -230,6 +364,21 @@ impl Ferrocene
     )
   }
 
+  pub fn addLayerFrame
+  (
+    &mut self,
+    listOfLayers:                       Vec<FrameID>,
+  ) -> FrameID
+  {
+    self.addFrame
+    (
+      Frame::newLayerFrame
+      (
+        listOfLayers,
+      )
+    )
+  }
+
   pub fn addParentFrame
   (
     &mut self,
@@ -259,23 +408,21 @@ impl Ferrocene
   (
     &mut self,
     frame:                              FrameID,
-  ) -> Result<&mut Frame, &str>
+  ) -> Result<&mut Frame, Error>
   {
-    if frame == 0
+    if !self.listOfFrames.isKnown ( frame )
     {
-      Err("UID of frame cannot be zero")
+      Err(Error::InvalidFrame(frame))
     }
-    else if frame > self.listOfFrames.len()
-    {
-      Err("UID of frame too high")
-    }
-    else if let Some(ref mut f) = self.listOfFrames [ frame - 1 ]
+    else if let Some(f) = self.listOfFrames.access ( frame )
     {
+      //the caller might change it, so it has to be drawn again.
+      f.setChanged(true);
       Ok(f)
     }
     else
     {
-      Err("UID of frame invalidated.")
+      Err(Error::InvalidatedFrame(frame))
     }
   }
 
@@ -283,23 +430,19 @@ impl Ferrocene
   (
     &mut self,
     display:                            DisplayID,
-  ) -> Result<&mut Display, &str>
+  ) -> Result<&mut Display, Error>
   {
-    if display == 0
+    if !self.listOfDisplays.isKnown ( display )
     {
-      Err("UID of display cannot be zero")
+      Err(Error::InvalidDisplay(display))
     }
-    else if display > self.listOfDisplays.len()
-    {
-      Err("UID of display too high")
-    }
-    else if let Some(ref mut d) = self.listOfDisplays [ display - 1 ]
+    else if let Some(d) = self.listOfDisplays.access ( display )
     {
       Ok(d)
     }
     else
     {
-      Err("UID of display invalidated.")
+      Err(Error::InvalidatedDisplay(display))
     }
   }
 
@@ -308,11 +451,23 @@ impl Ferrocene
     &mut self,
     display:                            DisplayID,
     title:                              String,
-  )
+  ) -> Result<(), Error>
   {
     let events                          =                                       self.sendChannel.clone();
-    let refDisplay                      =                                       self.accessDisplay ( display ).unwrap();
+    let refDisplay                      =                                       self.accessDisplay ( display )?;
     refDisplay.changeTitle( events, title );
+    Ok(())
+  }
+
+  pub fn setEscapeTimeout
+  (
+    &mut self,
+    display:                            DisplayID,
+    timeout:                            Duration,
+  ) -> Result<(), Error>
+  {
+    self.accessDisplay ( display )?.setEscapeTimeout ( timeout );
+    Ok(())
   }
 
   pub fn turnOnDisplay
@@ -321,14 +476,14 @@ impl Ferrocene
     display:                            DisplayID,
     frame:                              FrameID,
     title:                              String,
-  ) -> Result<FrameID, &'static str>
+  ) -> Result<FrameID, Error>
   {
     let events                          =                                       self.sendChannel.clone();
-    let refDisplay                      =                                       self.accessDisplay ( display ).unwrap();
+    let refDisplay                      =                                       self.accessDisplay ( display )?;
     let mut fine: bool                  =                                       false;
     if let Ok(mut focusedFrame) = refDisplay.focusedFrame.lock()
     {
-      refDisplay.flags                  |=                                      DisplayFlag::MaskRefresh;
+      refDisplay.flags                  |=                                      DisplayFlag::MaskRefresh | DisplayFlag::TurnedOn;
       refDisplay.mainFrame              =                                       frame;
       **focusedFrame                    =                                       frame;
       fine                              =                                       true;
@@ -340,7 +495,35 @@ impl Ferrocene
     }
     else
     {
-      Err("")
+      Err(Error::Poisoned(display))
+    }
+  }
+
+  //the frame gets the keys of the display from now on, FrameFocusLost and FrameFocusGained are sent right away,
+  //  even if the display is turned off.
+  pub fn focusFrame
+  (
+    &mut self,
+    display:                            DisplayID,
+    frame:                              FrameID,
+  ) -> Result<(), Error>
+  {
+    if !self.listOfFrames.isKnown ( frame )
+    {
+      return Err(Error::InvalidFrame(frame));
+    }
+    if self.listOfFrames.access ( frame ).is_none()
+    {
+      return Err(Error::InvalidatedFrame(frame));
+    }
+    let events                          =                                       self.sendChannel.clone();
+    if self.accessDisplay ( display )?.focusFrame ( &events, frame )
+    {
+      Ok(())
+    }
+    else
+    {
+      Err(Error::Poisoned(display))
     }
   }
 
@@ -348,46 +531,215 @@ impl Ferrocene
   (
     &mut self,
     display:                            DisplayID,
-  ) -> Result<FrameID, &'static str>
+  ) -> Result<FrameID, Error>
   {
     let events                          =                                       self.sendChannel.clone();
-    let refDisplay                      =                                       self.accessDisplay ( display ).unwrap();
+    let refDisplay                      =                                       self.accessDisplay ( display )?;
     let frame                           =                                       refDisplay.mainFrame;
+    refDisplay.flags                    &=                                      !DisplayFlag::TurnedOn;
     refDisplay.turnOff(events);
     Ok(frame)
   }
 
+  //like Ctrl-Z in the shell: all terminals are restored, until the process is continued.
+  //  then the displays are set up and drawn again and Suspended and Resumed are received.
+  #[cfg(feature = "display-tty")]
+  pub fn suspend
+  (
+    &self,
+  )
+  {
+    TTYDisplay::suspend();
+  }
+
   pub fn render
   (
     &mut self,
   )
   {
-    for display                         in                                      &mut self.listOfDisplays
+    let listOfChanged: Vec<FrameID>     =                                       self.listOfFrames.entries().filter ( | ( _, frame ) | frame.isChanged() ).map ( | ( frame, _ ) | frame ).collect();
+    //only frames with subframes can move other frames around.
+    let moved: bool                     =                                       self.listOfFrames.iter().any ( | frame | frame.isChanged() && !frame.isLeaf() );
+    for refDisplay                      in                                      self.listOfDisplays.iter_mut()
     {
       let events                        =                                       self.sendChannel.clone();
-      if let Some(ref mut refDisplay) = display
+      if ( refDisplay.flags & DisplayFlag::TurnedOn ) == DisplayFlag::None
+      {
+        continue;
+      }
+      if moved
+      {
+        refDisplay.flags                |=                                      DisplayFlag::NeedRemap;
+      }
+      //displays, that are not due yet, draw the changes later.
+      for frame                         in                                      listOfChanged.iter()
       {
-        if  ( refDisplay.flags & DisplayFlag::MaskRefresh ) != DisplayFlag::None
-        &&  ( refDisplay.lastRefresh.elapsed().unwrap() > refDisplay.nextRefresh )
+        if !refDisplay.changedFrames.contains ( frame )
         {
-          refDisplay.flags              &=                                      !DisplayFlag::NeedRefresh;
-          refDisplay.draw
+          refDisplay.changedFrames.push ( *frame );
+        }
+      }
+      refDisplay.update ( &events );
+      //due at the very time, nextRender() counts down to, or waitEvent() would wake up for nothing.
+      if refDisplay.lastRefresh.elapsed().unwrap_or_default() >= refDisplay.nextRefresh
+      {
+        let force: bool                 =                                       ( refDisplay.flags & DisplayFlag::MaskRefresh ) != DisplayFlag::None;
+        refDisplay.flags                &=                                      !DisplayFlag::NeedRefresh;
+        let drawn
+        = refDisplay.draw
           (
             &mut self.listOfFrames,
             &events,
             refDisplay.mainFrame,
             refDisplay.offsX,           refDisplay.offsY,
             refDisplay.sizeX,           refDisplay.sizeY,
+            force,
           );
-          #[cfg(any(feature = "display-tty"))]
+        if drawn
+        {
           match &mut refDisplay.display
           {
+            DisplayType::Memory(_)      =>                                      {},
             #[cfg(feature = "display-tty")]
-            DisplayType::TTY(ref mut output)  =>                                output.flush( events, refDisplay.this ),
+            DisplayType::TTY(ref mut output)
+                                        =>                                      output.flush( events, refDisplay.this ),
           }
           refDisplay.lastRefresh        =                                       SystemTime::now();
         }
       }
     }
+    for frame                           in                                      self.listOfFrames.iter_mut()
+    {
+      frame.setChanged(false);
+    }
   }
-}
\ No newline at end of file
+
+  //time until the next display, that is turned on and has something to draw, is due,
+  //  or None if there is nothing to draw at all.
+  pub fn nextRender
+  (
+    &self,
+  ) -> Option<Duration>
+  {
+    let changed: bool                   =                                       self.listOfFrames.iter().any ( Frame::isChanged );
+    self.listOfDisplays
+      .iter()
+      .filter
+      (
+        | display |
+              ( ( display.flags & DisplayFlag::TurnedOn     ) != DisplayFlag::None )
+          &&  ( ( display.flags & DisplayFlag::MaskRefresh  ) != DisplayFlag::None || changed || !display.changedFrames.is_empty() )
+      )
+      .map ( | display | display.nextRefresh.saturating_sub ( display.lastRefresh.elapsed().unwrap_or_default() ) )
+      .min()
+  }
+
+  //renders whatever is due and sleeps until either an event arrives or the next display is due,
+  //  until the timeout expires. Without timeout, this only returns, if there is an event.
+  //  gestures are returned right after the event, they were recognized from.
+  //  the events are numbered in the order returned here, their timestamps still tell, when the input was decoded.
+  pub fn waitEvent
+  (
+    &mut self,
+    timeout:                            Option<Duration>,
+  ) -> Option<Event>
+  {
+    let mut event: Event                =                                       self.receiveEvent ( timeout )?;
+    self.eventCounter                   +=                                      1;
+    event.sequence                      =                                       self.eventCounter;
+    Some(event)
+  }
+
+  fn receiveEvent
+  (
+    &mut self,
+    timeout:                            Option<Duration>,
+  ) -> Option<Event>
+  {
+    if let Some(event) = self.pendingEvents.pop_front()
+    {
+      return Some(event);
+    }
+    let deadline: Option<Instant>       =                                       timeout.map ( | timeout | Instant::now() + timeout );
+    loop
+    {
+      self.render();
+      let mut wait: Option<Duration>    =                                       self.nextRender();
+      if let Some(deadline) = deadline
+      {
+        let left: Duration              =                                       deadline.saturating_duration_since ( Instant::now() );
+        wait                            =                                       Some(wait.map_or ( left, | wait | wait.min ( left ) ));
+      }
+      let result
+      = match wait
+        {
+          Some(wait)                    =>                                      self.recvChannel.recv_timeout ( wait ),
+          None                          =>                                      self.recvChannel.recv().map_err ( | _ | RecvTimeoutError::Disconnected ),
+        };
+      match result
+      {
+        Ok(mut event)                   =>
+        {
+          //recorded as received, so a replay passes the same steps again.
+          let recorded: io::Result<()>  =                                       self.recorder.as_mut().map_or ( Ok(()), | recorder | recorder.record ( &event ) );
+          if let Err(error) = recorded
+          {
+            self.recorder               =                                       None;
+            self.pendingEvents.push_back
+            (
+              Event::new
+              (
+                EventType::Error(Error::IO(DisplayID::None, "cannot record event", error)),
+                DisplayID::None,        FrameID::None,
+                0,                      0,
+                MouseButton::None,
+              )
+            );
+          }
+          if event.isMouse()
+          {
+            if let Some(frame) = self.listOfFrames.access ( event.frame )
+            {
+              let ( offsX, offsY )      =                                       frame.scrollOffset();
+              event.localX              +=                                      offsX;
+              event.localY              +=                                      offsY;
+            }
+          }
+          if let Some(ref mut gestures) = self.gestures
+          {
+            self.pendingEvents.extend ( gestures.recognize ( &event ) );
+          }
+          return Some(event);
+        },
+        Err(RecvTimeoutError::Timeout)  =>
+        {
+          if deadline.is_some_and ( | deadline | Instant::now() >= deadline )
+          {
+            self.render();
+            return None;
+          }
+        },
+        Err(RecvTimeoutError::Disconnected)
+                                        =>                                      return None,
+      }
+    }
+  }
+
+  //passes every event to the handler, until it returns false.
+  pub fn run<Handler>
+  (
+    &mut self,
+    mut handler:                        Handler,
+  )
+  where
+    Handler:                            FnMut ( &mut Ferrocene, Event ) -> bool,
+  {
+    while let Some(event) = self.waitEvent ( None )
+    {
+      if !handler ( self, event )
+      {
+        break;
+      }
+    }
+  }
+}
@@ -0,0 +1,218 @@
+use std::
+{
+  fmt,
+};
+
+//identifies a frame or a display by its slot and the generation of this slot,
+//  so a stale UID cannot silently point to whatever reused the slot afterwards.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct UID
+{
+  pub index:                            usize,                                  // index plus one, zero is no UID at all
+  pub generation:                       usize,
+}
+
+impl UID
+{
+  pub const None:                       UID =                                   UID { index: 0, generation: 0 };
+
+  pub fn new
+  (
+    index:                              usize,
+    generation:                         usize,
+  ) -> Self
+  {
+    Self
+    {
+      index:                            index,
+      generation:                       generation,
+    }
+  }
+
+  pub fn isNone
+  (
+    &self,
+  ) -> bool
+  {
+    self.index == 0
+  }
+}
+
+impl fmt::Display for UID
+{
+  fn fmt
+  (
+    &self,
+    formatter:                          &mut fmt::Formatter,
+  ) -> fmt::Result
+  {
+    write!( formatter, "{}.{}", self.index, self.generation )
+  }
+}
+
+pub struct Slot<Type>
+{
+  pub generation:                       usize,
+  pub content:                          Option<Type>,                           // might be taken temporarily, e.g. while drawing
+}
+
+//list of slots, where removed contents leave a free slot behind, that will be reused with the next generation.
+pub struct Slots<Type>
+{
+  listOfSlots:                          Vec<Slot<Type>>,
+  listOfFreeSlots:                      Vec<usize>,
+}
+
+impl<Type> Slots<Type>
+{
+  pub fn new
+  (
+  ) -> Self
+  {
+    Self
+    {
+      listOfSlots:                      vec!(),
+      listOfFreeSlots:                  vec!(),
+    }
+  }
+
+  pub fn len
+  (
+    &self,
+  ) -> usize
+  {
+    self.listOfSlots.len() - self.listOfFreeSlots.len()
+  }
+
+  pub fn is_empty
+  (
+    &self,
+  ) -> bool
+  {
+    self.len() == 0
+  }
+
+  //whether the UID was ever handed out by this list, no matter if it is still valid.
+  pub fn isKnown
+  (
+    &self,
+    uid:                                UID,
+  ) -> bool
+  {
+    ( uid.index > 0 )
+    && ( uid.index <= self.listOfSlots.len() )
+    && ( uid.generation <= self.listOfSlots [ uid.index - 1 ].generation )
+  }
+
+  pub fn insert
+  (
+    &mut self,
+    content:                            Type,
+  ) -> UID
+  {
+    if let Some(index) = self.listOfFreeSlots.pop()
+    {
+      let slot                          =                                       &mut self.listOfSlots [ index ];
+      slot.content                      =                                       Some(content);
+      UID::new ( index + 1, slot.generation )
+    }
+    else
+    {
+      self.listOfSlots.push
+      (
+        Slot
+        {
+          generation:                   0,
+          content:                      Some(content),
+        }
+      );
+      UID::new ( self.listOfSlots.len(), 0 )
+    }
+  }
+
+  pub fn remove
+  (
+    &mut self,
+    uid:                                UID,
+  ) -> Option<Type>
+  {
+    let content                         =                                       self.slot ( uid )?.take()?;
+    let slot                            =                                       &mut self.listOfSlots [ uid.index - 1 ];
+    slot.generation                     +=                                      1;
+    self.listOfFreeSlots.push ( uid.index - 1 );
+    Some(content)
+  }
+
+  //the slot of the UID, unless it is zero, too high or of an older generation.
+  pub fn slot
+  (
+    &mut self,
+    uid:                                UID,
+  ) -> Option<&mut Option<Type>>
+  {
+    if ( uid.index > 0 )
+    && ( uid.index <= self.listOfSlots.len() )
+    && ( self.listOfSlots [ uid.index - 1 ].generation == uid.generation )
+    {
+      Some(&mut self.listOfSlots [ uid.index - 1 ].content)
+    }
+    else
+    {
+      None
+    }
+  }
+
+  pub fn access
+  (
+    &mut self,
+    uid:                                UID,
+  ) -> Option<&mut Type>
+  {
+    self.slot ( uid )?.as_mut()
+  }
+
+  pub fn iter
+  (
+    &self,
+  ) -> impl Iterator<Item = &Type>
+  {
+    self.listOfSlots.iter().filter_map ( | slot | slot.content.as_ref() )
+  }
+
+  pub fn iter_mut
+  (
+    &mut self,
+  ) -> impl Iterator<Item = &mut Type>
+  {
+    self.listOfSlots.iter_mut().filter_map ( | slot | slot.content.as_mut() )
+  }
+
+  //all contents with their UIDs.
+  pub fn entries
+  (
+    &mut self,
+  ) -> impl Iterator<Item = ( UID, &mut Type )>
+  {
+    self.listOfSlots
+      .iter_mut()
+      .enumerate()
+      .filter_map
+      (
+        | ( index, slot ) |
+        {
+          let uid                       =                                       UID::new ( index + 1, slot.generation );
+          slot.content.as_mut().map ( | content | ( uid, content ) )
+        }
+      )
+  }
+}
+
+impl<Type> Default for Slots<Type>
+{
+  fn default
+  (
+  ) -> Self
+  {
+    Self::new()
+  }
+}
@@ -0,0 +1,125 @@
+#![allow(dead_code)]
+
+use ferrocene::
+{
+  Ferrocene,
+  display::
+  {
+    DisplayFlag,
+    DisplayID,
+  },
+  frame::
+  {
+    FrameFlag,
+    FrameID,
+  },
+};
+use std::
+{
+  fs::
+  {
+    File,
+  },
+  io::
+  {
+    Read,
+  },
+  os::
+  {
+    unix::
+    {
+      io::
+      {
+        FromRawFd,
+      },
+    },
+  },
+  ptr,
+  thread,
+  time,
+};
+
+//a pseudo terminal of the given size: the master side is used by the test, the slave side by ferrocene.
+pub fn openTerminal
+(
+  sizeX:                                u16,
+  sizeY:                                u16,
+) -> ( File, File, File )
+{
+  unsafe
+  {
+    let mut master                      = 0;
+    let mut slave                       = 0;
+    let winsize
+    = libc::winsize
+      {
+        ws_row:                         sizeY,
+        ws_col:                         sizeX,
+        ws_xpixel:                      0,
+        ws_ypixel:                      0,
+      };
+    assert_eq!( libc::openpty ( &mut master, &mut slave, ptr::null_mut(), ptr::null(), &winsize ), 0 );
+    let flags                           = libc::fcntl ( master, libc::F_GETFL );
+    libc::fcntl ( master, libc::F_SETFL, flags | libc::O_NONBLOCK );
+    (
+      File::from_raw_fd ( master ),
+      File::from_raw_fd ( slave ),
+      File::from_raw_fd ( libc::dup ( slave ) ),
+    )
+  }
+}
+
+//everything the application has sent to the terminal so far.
+pub fn readTerminal
+(
+  master:                               &mut File,
+) -> String
+{
+  thread::sleep ( time::Duration::from_millis ( 50 ) );
+  let mut buffer                        = [ 0u8; 4096 ];
+  let mut output                        = vec!();
+  while let Ok(count) = master.read ( &mut buffer )
+  {
+    if count == 0
+    {
+      break;
+    }
+    output.extend_from_slice ( &buffer [ .. count ] );
+  }
+  String::from_utf8_lossy ( &output ).to_string()
+}
+
+//a ferrocene with a display on a pseudo terminal of 8 by 2 cells, that is not turned on yet.
+pub fn addDisplay
+(
+  flags:                                DisplayFlag,
+) -> ( File, Ferrocene, DisplayID )
+{
+  let ( master, input, output )         = openTerminal ( 8, 2 );
+  let mut myTUI                         = Ferrocene::new();
+  let ( _, _, myTerminal )
+  = myTUI.addTTYDisplay
+    (
+      flags,
+      0,                                0,
+      0,                                0,
+      Box::new ( input ),               Box::new ( output ),
+      0,
+    ).unwrap();
+  ( master, myTUI, myTerminal )
+}
+
+//the same display turned on with a text frame of these lines, with the setup already read from the terminal.
+pub fn openDisplay
+(
+  flags:                                DisplayFlag,
+  lines:                                &[&str],
+) -> ( File, Ferrocene, DisplayID, FrameID )
+{
+  let ( mut master, mut myTUI, myTerminal )
+                                        = addDisplay ( flags );
+  let theText                           = myTUI.addTextFrame ( FrameFlag::None, 0, 0, lines.iter().map ( | line | line.to_string() ).collect(), ' ' );
+  myTUI.turnOnDisplay ( myTerminal, theText, "Test".to_string() ).unwrap();
+  readTerminal ( &mut master );
+  ( master, myTUI, myTerminal, theText )
+}
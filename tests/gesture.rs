@@ -0,0 +1,171 @@
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+
+use ferrocene::
+{
+  Ferrocene,
+  event::
+  {
+    Event,
+    EventType,
+    MouseButton,
+    gesture::
+    {
+      GestureRecognizer,
+    },
+  },
+  frame::
+  {
+    FrameFlag,
+    FrameID,
+  },
+};
+use std::
+{
+  thread,
+  time::
+  {
+    Duration,
+  },
+};
+
+fn send
+(
+  myTUI:                                &Ferrocene,
+  event:                                EventType,
+  frame:                                FrameID,
+  x:                                    usize,
+  y:                                    usize,
+)
+{
+  myTUI.sendChannel.send ( Event::new ( event, ferrocene::DisplayID::None, frame, x, y, MouseButton::None ) ).unwrap();
+}
+
+fn receive
+(
+  myTUI:                                &mut Ferrocene,
+) -> Vec<EventType>
+{
+  let mut listOfEvents                  = vec!();
+  while let Some(event) = myTUI.waitEvent ( Some ( Duration::from_millis ( 10 ) ) )
+  {
+    listOfEvents.push ( event.event );
+  }
+  listOfEvents
+}
+
+#[test]
+fn clicksAreCounted()
+{
+  let mut myTUI                         = Ferrocene::new();
+  let theText                           = myTUI.addTextFrame ( FrameFlag::None, 0, 0, vec!(), ' ' );
+  myTUI.gestures                        = Some(GestureRecognizer::new());
+
+  for _                                 in 0 .. 3
+  {
+    send ( &myTUI, EventType::MouseLeftButtonPressed,  theText, 2, 1 );
+    send ( &myTUI, EventType::MouseLeftButtonReleased, theText, 2, 1 );
+  }
+  let listOfEvents                      = receive ( &mut myTUI );
+  assert_eq!( listOfEvents.len(), 9 );
+  assert!( matches!( listOfEvents [ 1 ], EventType::MouseEnter  ) );
+  assert!( matches!( listOfEvents [ 4 ], EventType::DoubleClick ) );
+  assert!( matches!( listOfEvents [ 7 ], EventType::TripleClick ) );
+
+  //too late for a double click.
+  thread::sleep ( Duration::from_millis ( 50 ) );
+  if let Some(ref mut gestures) = myTUI.gestures
+  {
+    gestures.clickInterval              = Duration::from_millis ( 20 );
+  }
+  send ( &myTUI, EventType::MouseLeftButtonPressed,  theText, 2, 1 );
+  send ( &myTUI, EventType::MouseLeftButtonReleased, theText, 2, 1 );
+  assert_eq!( receive ( &mut myTUI ).len(), 2 );
+}
+
+#[test]
+fn dragAndHover()
+{
+  let mut myTUI                         = Ferrocene::new();
+  let theSource                         = myTUI.addTextFrame ( FrameFlag::None, 0, 0, vec!(), ' ' );
+  let theTarget                         = myTUI.addTextFrame ( FrameFlag::None, 0, 0, vec!(), ' ' );
+  myTUI.gestures                        = Some(GestureRecognizer::new());
+
+  send ( &myTUI, EventType::MouseLeftButtonPressed,  theSource, 0, 0 );
+  send ( &myTUI, EventType::MouseMoveWithLeftButton, theSource, 1, 0 );
+  send ( &myTUI, EventType::MouseMoveWithLeftButton, theTarget, 5, 0 );
+  send ( &myTUI, EventType::MouseLeftButtonReleased, theTarget, 5, 0 );
+  let listOfEvents                      = receive ( &mut myTUI );
+  assert!( matches!( listOfEvents [  1 ], EventType::MouseEnter                                   ) );
+  assert!( matches!( listOfEvents [  3 ], EventType::DragStart { source } if source == theSource ) );
+  assert!( matches!( listOfEvents [  4 ], EventType::DragMove  { source } if source == theSource ) );
+  assert!( matches!( listOfEvents [  6 ], EventType::MouseLeave                                   ) );
+  assert!( matches!( listOfEvents [  7 ], EventType::MouseEnter                                   ) );
+  assert!( matches!( listOfEvents [  8 ], EventType::DragMove  { source } if source == theSource ) );
+  assert!( matches!( listOfEvents [ 10 ], EventType::DragEnd   { source } if source == theSource ) );
+  assert_eq!( listOfEvents.len(), 11 );
+}
+
+#[test]
+fn clicksAreTimedByTimestamps()
+{
+  let mut myTUI                         = Ferrocene::new();
+  let theText                           = myTUI.addTextFrame ( FrameFlag::None, 0, 0, vec!(), ' ' );
+  myTUI.gestures                        = Some(GestureRecognizer::new());
+
+  //sent right after each other, but pressed a second apart.
+  let mut first                         = Event::new ( EventType::MouseLeftButtonPressed, ferrocene::DisplayID::None, theText, 2, 1, MouseButton::None );
+  first.timestamp                       -= Duration::from_secs ( 1 );
+  myTUI.sendChannel.send ( first ).unwrap();
+  send ( &myTUI, EventType::MouseLeftButtonPressed, theText, 2, 1 );
+  let mut listOfEvents                  = vec!();
+  while let Some(event) = myTUI.waitEvent ( Some ( Duration::from_millis ( 10 ) ) )
+  {
+    listOfEvents.push ( event );
+  }
+  assert_eq!( listOfEvents.len(), 3 );
+  assert!( !listOfEvents.iter().any ( | event | matches!( event.event, EventType::DoubleClick ) ) );
+  //gestures are numbered right after the event, they were recognized from.
+  for ( index, event )                  in listOfEvents.iter().enumerate()
+  {
+    assert_eq!( event.sequence, index as u64 + 1 );
+  }
+  assert_eq!( listOfEvents [ 1 ].timestamp, listOfEvents [ 0 ].timestamp );
+}
+
+#[test]
+fn gesturesArePlacedInTheirFrame()
+{
+  let mut myTUI                         = Ferrocene::new();
+  let theSource                         = myTUI.addTextFrame ( FrameFlag::None, 0, 0, vec!(), ' ' );
+  let theTarget                         = myTUI.addTextFrame ( FrameFlag::None, 0, 0, vec!(), ' ' );
+  myTUI.gestures                        = Some(GestureRecognizer::new());
+
+  //the source begins at 10,5 and the target at 30,2 on the screen.
+  for ( event, frame, x, y, localX )    in [ ( EventType::MouseLeftButtonPressed,  theSource, 11, 5, 1 ),
+                                             ( EventType::MouseMoveWithLeftButton, theSource, 12, 5, 2 ),
+                                             ( EventType::MouseMoveWithLeftButton, theTarget, 31, 2, 1 ) ]
+  {
+    let mut event                       = Event::new ( event, ferrocene::DisplayID::None, frame, x, y, MouseButton::None );
+    event.localX                        = localX;
+    myTUI.sendChannel.send ( event ).unwrap();
+  }
+  let mut listOfEvents                  = vec!();
+  while let Some(event) = myTUI.waitEvent ( Some ( Duration::from_millis ( 10 ) ) )
+  {
+    listOfEvents.push ( event );
+  }
+  assert_eq!( listOfEvents.len(), 9 );
+  assert!( matches!( listOfEvents [ 3 ].event, EventType::DragStart { .. } ) );
+  assert_eq!( ( listOfEvents [ 3 ].localX, listOfEvents [ 3 ].localY ), ( 2, 0 ) );
+  //the pointer left the source at 31,2, which is right of and above it.
+  assert!( matches!( listOfEvents [ 6 ].event, EventType::MouseLeave ) );
+  assert_eq!( listOfEvents [ 6 ].frame, theSource );
+  assert_eq!( ( listOfEvents [ 6 ].localX, listOfEvents [ 6 ].localY ), ( 21, -3 ) );
+  assert!( matches!( listOfEvents [ 7 ].event, EventType::MouseEnter ) );
+  assert_eq!( listOfEvents [ 7 ].frame, theTarget );
+  assert_eq!( ( listOfEvents [ 7 ].localX, listOfEvents [ 7 ].localY ), ( 1, 0 ) );
+  assert!( matches!( listOfEvents [ 8 ].event, EventType::DragMove { source } if source == theSource ) );
+  assert_eq!( listOfEvents [ 8 ].frame, theTarget );
+  assert_eq!( ( listOfEvents [ 8 ].localX, listOfEvents [ 8 ].localY ), ( 1, 0 ) );
+}
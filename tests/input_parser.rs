@@ -0,0 +1,128 @@
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+
+use ferrocene::
+{
+  InputParser,
+  display::
+  {
+    DisplayID,
+  },
+  event::
+  {
+    EventType,
+    KeyAction,
+    Modifier,
+  },
+  frame::
+  {
+    FrameID,
+  },
+};
+
+#[test]
+fn keysAreDecoded()
+{
+  let mut parser                        = InputParser::new();
+  let listOfEvents                      = parser.parse ( b"a\x1b[1;5A\x1b[97;1:3u\xc3\xa4" );
+  assert_eq!( listOfEvents.len(), 4 );
+  assert!( matches!( listOfEvents [ 0 ].event, EventType::Character('a') ) );
+  assert!( matches!( listOfEvents [ 1 ].event, EventType::Up ) );
+  assert_eq!( listOfEvents [ 1 ].modifiers, Modifier::Ctrl );
+  assert!( matches!( listOfEvents [ 2 ].event, EventType::Character('a') ) );
+  assert_eq!( listOfEvents [ 2 ].action, KeyAction::Release );
+  assert!( matches!( listOfEvents [ 3 ].event, EventType::Character('ä') ) );
+  for event                             in listOfEvents.iter()
+  {
+    assert_eq!( event.display, DisplayID::None );
+    assert_eq!( event.frame, FrameID::None );
+  }
+}
+
+#[test]
+fn partialSequencesAreKept()
+{
+  let mut parser                        = InputParser::new();
+  assert!( parser.parse ( b"\x1b[<0;5" ).is_empty() );
+  assert!( parser.parse ( b";3" ).is_empty() );
+  let listOfEvents                      = parser.parse ( b"M\xc3" );
+  assert_eq!( listOfEvents.len(), 1 );
+  assert!( matches!( listOfEvents [ 0 ].event, EventType::MouseLeftButtonPressed ) );
+  assert_eq!( ( listOfEvents [ 0 ].cursorX, listOfEvents [ 0 ].cursorY ), ( 4, 2 ) );
+  let listOfEvents                      = parser.parse ( b"\xa4" );
+  assert!( matches!( listOfEvents [ 0 ].event, EventType::Character('ä') ) );
+}
+
+#[test]
+fn escapeTimesOut()
+{
+  let mut parser                        = InputParser::new();
+  assert!( parser.parse ( b"\x1b" ).is_empty() );
+  assert!( parser.isAmbiguous() );
+  let event                             = parser.timeout().unwrap();
+  assert!( matches!( event.event, EventType::Escape ) );
+  assert!( !parser.isAmbiguous() );
+  assert!( parser.timeout().is_none() );
+}
+
+#[test]
+fn cursorPositionIsAnswered()
+{
+  let mut parser                        = InputParser::new();
+  parser.display                        = DisplayID::new ( 3, 1 );
+  assert!( parser.parse ( b"\x1b[24;" ).is_empty() );
+  let listOfEvents                      = parser.parse ( b"80R" );
+  assert_eq!( listOfEvents.len(), 1 );
+  assert!( matches!( listOfEvents [ 0 ].event, EventType::CursorPosition ) );
+  assert_eq!( ( listOfEvents [ 0 ].cursorX, listOfEvents [ 0 ].cursorY ), ( 80, 24 ) );
+  assert_eq!( listOfEvents [ 0 ].display, DisplayID::new ( 3, 1 ) );
+}
+
+#[test]
+fn pasteIsHeldBackOnlyUntilTimeout()
+{
+  let mut parser                        = InputParser::new();
+  //ctrl-z and escape are pasted like any other byte.
+  assert!( parser.parse ( b"\x1b[200~a\x1a\x1bb\xc3" ).is_empty() );
+  assert!( parser.isPasting() );
+  assert!( !parser.isAmbiguous() );
+  let event                             = parser.timeout().unwrap();
+  assert!( matches!( event.event, EventType::Paste(ref text) if text == "a\x1a\x1bb" ) );
+  //the begun character was kept, and the end of the paste may still follow.
+  assert!( parser.parse ( b"\xa4c\x1b[2" ).is_empty() );
+  let listOfEvents                      = parser.parse ( b"01~x" );
+  assert_eq!( listOfEvents.len(), 2 );
+  assert!( matches!( listOfEvents [ 0 ].event, EventType::Paste(ref text) if text == "äc" ) );
+  assert!( matches!( listOfEvents [ 1 ].event, EventType::Character('x') ) );
+  assert!( !parser.isPasting() );
+}
+
+#[test]
+fn longPasteComesInParts()
+{
+  let mut parser                        = InputParser::new();
+  let mut input                         = b"\x1b[200~".to_vec();
+  input.extend ( "ö".repeat ( 1 << 19 ).bytes() );
+  input.extend ( b"x\x1b[201~" );
+  let listOfEvents                      = parser.parse ( &input );
+  assert_eq!( listOfEvents.len(), 2 );
+  assert!( matches!( listOfEvents [ 0 ].event, EventType::Paste(ref text) if *text == "ö".repeat ( 1 << 19 ) ) );
+  assert!( matches!( listOfEvents [ 1 ].event, EventType::Paste(ref text) if text == "x" ) );
+}
+
+#[test]
+fn hugeParametersDoNotPanic()
+{
+  let huge                              = "9".repeat ( 30 );
+  let mut parser                        = InputParser::new();
+  for sequence                          in [ "\x1b[{}A", "\x1b[1;{}A", "\x1b[{};{}R", "\x1b[{}~", "\x1b[{};{}~", "\x1b[{}u", "\x1b[97;{}u", "\x1b[97;1:{}u",
+                                             "\x1b[<{};{};{}M", "\x1b[<0;{};{}m", "\x1b[?{}u", "\x1b[?{};{}c", "\x1b[{}:{}u", "\x1b[1;{}P", "\x1bO{}A" ]
+  {
+    parser.parse ( sequence.replace ( "{}", &huge ).as_bytes() );
+  }
+  //the parser is still in sync afterwards.
+  let listOfEvents                      = parser.parse ( b"\x1b[Ba" );
+  assert_eq!( listOfEvents.len(), 2 );
+  assert!( matches!( listOfEvents [ 0 ].event, EventType::Down ) );
+  assert!( matches!( listOfEvents [ 1 ].event, EventType::Character('a') ) );
+}
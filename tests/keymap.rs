@@ -0,0 +1,160 @@
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+
+use ferrocene::
+{
+  InputParser,
+  event::
+  {
+    Event,
+    EventType,
+    KeyAction,
+    Modifier,
+    MouseButton,
+    keymap::
+    {
+      Key,
+      KeyResult,
+      KeyStroke,
+      Keymap,
+    },
+  },
+  frame::
+  {
+    Frame,
+    FrameFlag,
+    FrameID,
+  },
+};
+
+fn key
+(
+  event:                                EventType,
+  frame:                                FrameID,
+) -> Event
+{
+  Event::new ( event, ferrocene::DisplayID::None, frame, 0, 0, MouseButton::None )
+}
+
+fn chord
+(
+  text:                                 &str,
+) -> Vec<KeyStroke>
+{
+  KeyStroke::parseChord ( text ).unwrap()
+}
+
+#[test]
+fn strokesAreParsed()
+{
+  assert_eq!( KeyStroke::parse ( "Ctrl-x" ), Some(KeyStroke::new ( Key::Character('x'), Modifier::Ctrl )) );
+  assert_eq!( KeyStroke::parse ( "Shift-a" ), Some(KeyStroke::new ( Key::Character('A'), Modifier::None )) );
+  assert_eq!( KeyStroke::parse ( "Alt-Shift-Up" ), Some(KeyStroke::new ( Key::Up, Modifier::Alt | Modifier::Shift )) );
+  assert_eq!( KeyStroke::parse ( "F12" ), Some(KeyStroke::new ( Key::Function(12), Modifier::None )) );
+  assert_eq!( KeyStroke::parse ( "Ctrl-Space" ), Some(KeyStroke::new ( Key::Character(' '), Modifier::Ctrl )) );
+  assert_eq!( KeyStroke::parse ( "Foo-x" ), None );
+  assert_eq!( KeyStroke::parse ( "Enter" ), None );
+  assert_eq!( KeyStroke::parseChord ( "" ), None );
+  for text                              in [ "Ctrl-x", "Alt-Shift-Up", "F5", "Ctrl-Minus", "q" ]
+  {
+    assert_eq!( KeyStroke::parse ( text ).unwrap().to_string(), text );
+  }
+}
+
+#[test]
+fn eventsBecomeStrokes()
+{
+  let stroke                            = KeyStroke::parse ( "Ctrl-x" );
+  assert_eq!( KeyStroke::fromEvent ( &key ( EventType::Ctrl('x'), FrameID::None ) ), stroke );
+  let mut event                         = key ( EventType::Character('x'), FrameID::None );
+  event.modifiers                       = Modifier::Ctrl | Modifier::NumLock;
+  assert_eq!( KeyStroke::fromEvent ( &event ), stroke );
+  event.action                          = KeyAction::Release;
+  assert_eq!( KeyStroke::fromEvent ( &event ), None );
+  assert_eq!( KeyStroke::fromEvent ( &key ( EventType::MouseWheelUp, FrameID::None ) ), None );
+}
+
+#[test]
+fn shiftedCtrlStrokesMatch()
+{
+  let stroke                            = KeyStroke::parse ( "Ctrl-Shift-x" );
+  assert_eq!( stroke, Some(KeyStroke::new ( Key::Character('x'), Modifier::Ctrl | Modifier::Shift )) );
+  assert_eq!( KeyStroke::parse ( "Ctrl-X" ), stroke );
+  assert_eq!( stroke.unwrap().to_string(), "Ctrl-Shift-x" );
+  assert_eq!( KeyStroke::parse ( "Alt-Shift-x" ), KeyStroke::parse ( "Alt-X" ) );
+
+  let mut keymap: Keymap<&str>          = Keymap::new();
+  keymap.bind ( FrameID::None, chord ( "Ctrl-Shift-x" ), "cut" );
+  keymap.bind ( FrameID::None, chord ( "Alt-X" ), "execute" );
+  //as sent with the kitty keyboard protocol, and by legacy terminals for Alt-X.
+  let mut parser                        = InputParser::new();
+  let listOfEvents                      = parser.parse ( b"\x1b[120;6u\x1b[120;4u\x1bX\x18" );
+  assert_eq!( listOfEvents.len(), 4 );
+  assert_eq!( keymap.feed ( &listOfEvents [ 0 ] ), KeyResult::Action("cut") );
+  assert_eq!( keymap.feed ( &listOfEvents [ 1 ] ), KeyResult::Action("execute") );
+  assert_eq!( keymap.feed ( &listOfEvents [ 2 ] ), KeyResult::Action("execute") );
+  assert_eq!( keymap.feed ( &listOfEvents [ 3 ] ), KeyResult::Unbound );
+}
+
+#[test]
+fn chordsAreCompleted()
+{
+  let mut keymap: Keymap<&str>          = Keymap::new();
+  assert_eq!( keymap.bind ( FrameID::None, chord ( "Ctrl-x Ctrl-s" ), "save" ), None );
+  assert_eq!( keymap.bind ( FrameID::None, chord ( "Ctrl-x Ctrl-c" ), "quit" ), None );
+  assert_eq!( keymap.bind ( FrameID::None, chord ( "q" ), "quit" ), None );
+  assert_eq!( keymap.feed ( &key ( EventType::Ctrl('x'), FrameID::None ) ), KeyResult::Pending );
+  assert_eq!( keymap.pendingText(), "Ctrl-x " );
+  assert_eq!( keymap.feed ( &key ( EventType::Ctrl('s'), FrameID::None ) ), KeyResult::Action("save") );
+  assert!( keymap.pending().is_empty() );
+  assert_eq!( keymap.feed ( &key ( EventType::Character('q'), FrameID::None ) ), KeyResult::Action("quit") );
+  assert_eq!( keymap.feed ( &key ( EventType::Character('w'), FrameID::None ) ), KeyResult::Unbound );
+  assert_eq!( keymap.feed ( &key ( EventType::Ctrl('x'), FrameID::None ) ), KeyResult::Pending );
+  assert_eq!( keymap.feed ( &key ( EventType::Character('w'), FrameID::None ) ), KeyResult::Cancelled );
+  assert!( keymap.pending().is_empty() );
+  assert_eq!( keymap.unbind ( FrameID::None, &chord ( "q" ) ), Some("quit") );
+  assert_eq!( keymap.feed ( &key ( EventType::Character('q'), FrameID::None ) ), KeyResult::Unbound );
+}
+
+#[test]
+fn framesFallThrough()
+{
+  let frame                             = FrameID::new ( 1, 1 );
+  let other                             = FrameID::new ( 2, 1 );
+  let mut keymap: Keymap<&str>          = Keymap::new();
+  keymap.bind ( FrameID::None, chord ( "q" ), "quit" );
+  keymap.bind ( FrameID::None, chord ( "Ctrl-x k" ), "kill" );
+  keymap.bind ( frame, chord ( "q" ), "close" );
+  assert_eq!( keymap.bind ( frame, chord ( "q" ), "hide" ), Some("close") );
+  assert_eq!( keymap.feed ( &key ( EventType::Character('q'), frame ) ), KeyResult::Action("hide") );
+  assert_eq!( keymap.feed ( &key ( EventType::Character('q'), other ) ), KeyResult::Action("quit") );
+  assert_eq!( keymap.feed ( &key ( EventType::Ctrl('x'), frame ) ), KeyResult::Pending );
+  assert_eq!( keymap.feed ( &key ( EventType::Character('k'), frame ) ), KeyResult::Action("kill") );
+  keymap.removeScope ( frame );
+  assert_eq!( keymap.feed ( &key ( EventType::Character('q'), frame ) ), KeyResult::Action("quit") );
+}
+
+#[test]
+fn pendingChordIsShown()
+{
+  let mut keymap: Keymap<u8>            = Keymap::new();
+  keymap.bind ( FrameID::None, chord ( "Ctrl-x Alt-F4" ), 1 );
+  let mut status                        = Frame::newStatusFrame ( FrameFlag::None, 0, String::new(), ' ' );
+  if let Frame::Status(ref mut status) = status
+  {
+    status.changed                      = false;
+    keymap.showPending ( status );
+    assert!( !status.changed );
+    keymap.feed ( &key ( EventType::Ctrl('x'), FrameID::None ) );
+    keymap.showPending ( status );
+    assert_eq!( status.text, "Ctrl-x " );
+    assert!( status.changed );
+    keymap.cancel();
+    keymap.showPending ( status );
+    assert_eq!( status.text, "" );
+  }
+  else
+  {
+    panic!( "not a status frame" );
+  }
+}
@@ -0,0 +1,58 @@
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+
+use ferrocene::
+{
+  Error,
+  Ferrocene,
+  display::
+  {
+    DisplayFlag,
+  },
+  frame::
+  {
+    FrameFlag,
+    FrameID,
+  },
+};
+
+#[test]
+fn staleFrameIDs()
+{
+  let mut myTUI                         = Ferrocene::new();
+  let thePopup                          = myTUI.addTextFrame ( FrameFlag::None, 0, 0, vec!(), ' ' );
+  let theStatusBar                      = myTUI.addStatusFrame ( FrameFlag::None, 0, "".to_string(), ' ' );
+
+  assert!( myTUI.removeFrame ( thePopup ).is_ok() );
+  assert!( matches!( myTUI.accessFrame ( thePopup ),     Err(Error::InvalidatedFrame(_)) ) );
+  assert!( matches!( myTUI.removeFrame ( thePopup ),     Err(Error::InvalidatedFrame(_)) ) );
+  assert!( matches!( myTUI.accessFrame ( FrameID::None ), Err(Error::InvalidFrame(_)) ) );
+
+  //the slot is reused, but the old UID must not point to the new frame.
+  let theNextPopup                      = myTUI.addTextFrame ( FrameFlag::None, 0, 0, vec!(), ' ' );
+  assert_eq!( theNextPopup.index, thePopup.index );
+  assert_ne!( theNextPopup, thePopup );
+  assert!( myTUI.accessFrame ( theNextPopup ).is_ok() );
+  assert!( myTUI.accessFrame ( thePopup ).is_err() );
+  assert!( myTUI.accessFrame ( theStatusBar ).is_ok() );
+  assert_eq!( myTUI.listOfFrames.len(), 2 );
+}
+
+#[test]
+fn staleDisplayIDs()
+{
+  let mut myTUI                         = Ferrocene::new();
+  let theText                           = myTUI.addTextFrame ( FrameFlag::None, 0, 0, vec!( "x".to_string() ), ' ' );
+  let myDisplay                         = myTUI.addMemoryDisplay ( DisplayFlag::None, 0, 0, 2, 1, 0 );
+  myTUI.turnOnDisplay ( myDisplay, theText, "Test".to_string() ).unwrap();
+  myTUI.render();
+
+  assert!( myTUI.removeDisplay ( myDisplay ).is_ok() );
+  assert!( matches!( myTUI.accessDisplay ( myDisplay ), Err(Error::InvalidatedDisplay(_)) ) );
+  assert!( matches!( myTUI.turnOffDisplay ( myDisplay ), Err(Error::InvalidatedDisplay(_)) ) );
+
+  let myNextDisplay                     = myTUI.addMemoryDisplay ( DisplayFlag::None, 0, 0, 2, 1, 0 );
+  assert_eq!( myTUI.accessDisplay ( myNextDisplay ).unwrap().this, myNextDisplay );
+  assert!( myTUI.accessDisplay ( myDisplay ).is_err() );
+  myTUI.render();
+}
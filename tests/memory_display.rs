@@ -0,0 +1,334 @@
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+
+use ferrocene::
+{
+  Error,
+  Ferrocene,
+  display::
+  {
+    DisplayFlag,
+    DisplayType,
+  },
+  event::
+  {
+    Event,
+    EventType,
+    MouseButton,
+  },
+  frame::
+  {
+    Frame,
+    FrameFlag,
+    FrameID,
+    Tiling,
+    style::
+    {
+      Colour,
+      Style,
+      StyledToken,
+    },
+  },
+};
+use std::
+{
+  thread,
+  time::
+  {
+    Duration,
+    Instant,
+  },
+};
+
+fn lines
+(
+  myTUI:                                &mut Ferrocene,
+  display:                              ferrocene::DisplayID,
+) -> Vec<String>
+{
+  match myTUI.accessDisplay ( display ).unwrap().display
+  {
+    DisplayType::Memory(ref memory)     =>                                      ( 0 .. memory.buffer.sizeY ).map ( | y | memory.line ( y ) ).collect(),
+    #[allow(unreachable_patterns)]
+    _                                   =>                                      panic!("not a memory display"),
+  }
+}
+
+#[test]
+fn gridLayout()
+{
+  let mut myTUI                         = Ferrocene::new();
+  let myDisplay                         = myTUI.addMemoryDisplay ( DisplayFlag::None, 0, 0, 10, 3, 0 );
+  let theStatusBar                      = myTUI.addStatusFrame ( FrameFlag::None, -1, "ok".to_string(), '_' );
+  let theText
+  = myTUI.addTextFrame
+    (
+      FrameFlag::None,
+      0,                                0,
+      vec!( "Hello".to_string(), "World!".to_string(), "hidden".to_string() ),
+      '.',
+    );
+  let theScreen
+  = myTUI.addParentFrame
+    (
+      Tiling::Grid,
+      vec!
+      (
+        Frame::newInstance ( theText,      0, 0, 10, 2, 10, 2, 10, 2, 0, 0, 1, 1 ),
+        Frame::newInstance ( theStatusBar, 0, 2, 10, 1, 10, 1, 10, 1, 0, 1, 1, 1 ),
+      ),
+      vec!( 0, 10 ),
+      vec!( 0, 2, 3 ),
+      vec!( 1 ),
+      vec!( 1, 1 ),
+      FrameID::None,
+    );
+  myTUI.turnOnDisplay ( myDisplay, theScreen, "Test".to_string() ).unwrap();
+  myTUI.render();
+
+  assert_eq!
+  (
+    lines ( &mut myTUI, myDisplay ),
+    vec!( "Hello.....", "World!....", "_ok_______" ),
+  );
+}
+
+#[test]
+fn editorStyles()
+{
+  let mut myTUI                         = Ferrocene::new();
+  let myDisplay                         = myTUI.addMemoryDisplay ( DisplayFlag::None, 0, 0, 4, 1, 0 );
+  let theEditor
+  = myTUI.addEditorFrame
+    (
+      FrameFlag::None,
+      1,                                0,
+      vec!
+      (
+        vec!
+        (
+          StyledToken::new ( "ab".to_string(), Style::None,   0, Colour::Green, Colour::Black ),
+          StyledToken::new ( "c".to_string(),  Style::Italic, 0, Colour::Red,   Colour::Blue  ),
+        ),
+      ),
+      ' ',
+    );
+  myTUI.turnOnDisplay ( myDisplay, theEditor, "Test".to_string() ).unwrap();
+  myTUI.render();
+
+  match myTUI.accessDisplay ( myDisplay ).unwrap().display
+  {
+    DisplayType::Memory(ref memory)     =>
+    {
+      assert_eq!( memory.line ( 0 ), "bc  " );
+      let first                         = memory.cell ( 0, 0 ).unwrap();
+      assert_eq!( first.fgColour, Colour::Green );
+      let second                        = memory.cell ( 1, 0 ).unwrap();
+      assert_eq!( second.flags,    Style::Italic );
+      assert_eq!( second.bgColour, Colour::Blue  );
+      assert_eq!( memory.title,    "Test"        );
+    },
+    #[allow(unreachable_patterns)]
+    _                                   =>                                      panic!("not a memory display"),
+  }
+}
+
+#[test]
+fn onlyChangedFramesAreDrawn()
+{
+  let mut myTUI                         = Ferrocene::new();
+  let myDisplay                         = myTUI.addMemoryDisplay ( DisplayFlag::None, 0, 0, 4, 2, 0 );
+  let theStatusBar                      = myTUI.addStatusFrame ( FrameFlag::None, 0, "1s".to_string(), ' ' );
+  let theText                           = myTUI.addTextFrame ( FrameFlag::None, 0, 0, vec!( "old".to_string() ), ' ' );
+  let theScreen
+  = myTUI.addParentFrame
+    (
+      Tiling::Grid,
+      vec!
+      (
+        Frame::newInstance ( theText,      0, 0, 4, 1, 4, 1, 4, 1, 0, 0, 1, 1 ),
+        Frame::newInstance ( theStatusBar, 0, 1, 4, 1, 4, 1, 4, 1, 0, 1, 1, 1 ),
+      ),
+      vec!( 0, 4 ),
+      vec!( 0, 1, 2 ),
+      vec!( 1 ),
+      vec!( 1, 1 ),
+      FrameID::None,
+    );
+  myTUI.turnOnDisplay ( myDisplay, theScreen, "Test".to_string() ).unwrap();
+  myTUI.render();
+  assert_eq!( lines ( &mut myTUI, myDisplay ), vec!( "old ", "1s  " ) );
+
+  //changed behind the back of accessFrame, so it is still considered clean.
+  if let Some(Frame::Text ( ref mut frame )) = myTUI.listOfFrames.access ( theText )
+  {
+    frame.lines                         = vec!( "new".to_string() );
+  }
+  if let Frame::Status ( ref mut frame ) = myTUI.accessFrame ( theStatusBar ).unwrap()
+  {
+    frame.text                          = "2s".to_string();
+  }
+  myTUI.render();
+  assert_eq!( lines ( &mut myTUI, myDisplay ), vec!( "old ", "2s  " ) );
+
+  myTUI.accessFrame ( theText ).unwrap();
+  myTUI.render();
+  assert_eq!( lines ( &mut myTUI, myDisplay ), vec!( "new ", "2s  " ) );
+}
+
+#[test]
+fn changesFasterThanTheRefreshAreDrawnLater()
+{
+  let mut myTUI                         = Ferrocene::new();
+  let myDisplay                         = myTUI.addMemoryDisplay ( DisplayFlag::None, 0, 0, 4, 2, 50_000_000 );
+  let theStatusBar                      = myTUI.addStatusFrame ( FrameFlag::None, 0, "1s".to_string(), ' ' );
+  let theText                           = myTUI.addTextFrame ( FrameFlag::None, 0, 0, vec!( "old".to_string() ), ' ' );
+  let theScreen
+  = myTUI.addParentFrame
+    (
+      Tiling::Grid,
+      vec!
+      (
+        Frame::newInstance ( theText,      0, 0, 4, 1, 4, 1, 4, 1, 0, 0, 1, 1 ),
+        Frame::newInstance ( theStatusBar, 0, 1, 4, 1, 4, 1, 4, 1, 0, 1, 1, 1 ),
+      ),
+      vec!( 0, 4 ),
+      vec!( 0, 1, 2 ),
+      vec!( 1 ),
+      vec!( 1, 1 ),
+      FrameID::None,
+    );
+  myTUI.turnOnDisplay ( myDisplay, theScreen, "Test".to_string() ).unwrap();
+  thread::sleep ( Duration::from_millis ( 60 ) );
+  myTUI.render();
+  assert_eq!( lines ( &mut myTUI, myDisplay ), vec!( "old ", "1s  " ) );
+
+  //the status bar ticks twice before the display is due again.
+  for text                              in [ "2s", "3s" ]
+  {
+    if let Frame::Status ( ref mut frame ) = myTUI.accessFrame ( theStatusBar ).unwrap()
+    {
+      frame.text                        = text.to_string();
+    }
+    myTUI.render();
+    assert_eq!( lines ( &mut myTUI, myDisplay ), vec!( "old ", "1s  " ) );
+  }
+  if let Some(Frame::Text ( ref mut frame )) = myTUI.listOfFrames.access ( theText )
+  {
+    frame.lines                         = vec!( "new".to_string() );
+  }
+  assert!( myTUI.nextRender().is_some() );
+  thread::sleep ( Duration::from_millis ( 60 ) );
+  myTUI.render();
+  assert_eq!( lines ( &mut myTUI, myDisplay ), vec!( "old ", "3s  " ) );
+  assert!( myTUI.nextRender().is_none() );
+}
+
+#[test]
+fn waitForEventsOrRefresh()
+{
+  let mut myTUI                         = Ferrocene::new();
+  let myDisplay                         = myTUI.addMemoryDisplay ( DisplayFlag::None, 0, 0, 3, 1, 20_000_000 );
+  let theText                           = myTUI.addTextFrame ( FrameFlag::None, 0, 0, vec!( "a".to_string() ), ' ' );
+  myTUI.turnOnDisplay ( myDisplay, theText, "Test".to_string() ).unwrap();
+  assert!( myTUI.nextRender().is_some() );
+
+  //nothing happens, but the pending refresh is done while waiting.
+  let start                             = Instant::now();
+  assert!( myTUI.waitEvent ( Some ( Duration::from_millis ( 50 ) ) ).is_none() );
+  assert!( start.elapsed() >= Duration::from_millis ( 50 ) );
+  assert_eq!( lines ( &mut myTUI, myDisplay ), vec!( "a  " ) );
+  assert!( myTUI.nextRender().is_none() );
+
+  myTUI.sendChannel.send ( Event::new ( EventType::Character('x'), myDisplay, theText, 0, 0, MouseButton::None ) ).unwrap();
+  let mut count                         = 0;
+  myTUI.run
+  (
+    | _, event |
+    {
+      count                             += 1;
+      !matches!( event.event, EventType::Character('x') )
+    }
+  );
+  assert_eq!( count, 1 );
+}
+
+#[test]
+fn resizeRedrawsEverything()
+{
+  let mut myTUI                         = Ferrocene::new();
+  let myDisplay                         = myTUI.addMemoryDisplay ( DisplayFlag::None, 0, 0, 3, 1, 0 );
+  let theText                           = myTUI.addTextFrame ( FrameFlag::None, 0, 0, vec!( "Hello".to_string(), "World".to_string() ), '.' );
+  myTUI.turnOnDisplay ( myDisplay, theText, "Test".to_string() ).unwrap();
+  myTUI.render();
+  assert_eq!( lines ( &mut myTUI, myDisplay ), vec!( "Hel" ) );
+
+  let events                            = myTUI.sendChannel.clone();
+  myTUI.accessDisplay ( myDisplay ).unwrap().resize ( &events, 6, 3 );
+  myTUI.render();
+  assert_eq!( lines ( &mut myTUI, myDisplay ), vec!( "Hello.", "World.", "......" ) );
+}
+
+#[test]
+fn topmostFrameIsMapped()
+{
+  let mut myTUI                         = Ferrocene::new();
+  let myDisplay                         = myTUI.addMemoryDisplay ( DisplayFlag::None, 0, 0, 4, 2, 0 );
+  let theStatusBar                      = myTUI.addStatusFrame ( FrameFlag::None, 0, "ok".to_string(), ' ' );
+  let theText                           = myTUI.addTextFrame ( FrameFlag::None, 0, 0, vec!( "abc".to_string() ), ' ' );
+  let thePopup                          = myTUI.addTextFrame ( FrameFlag::None, 0, 0, vec!( "x".to_string() ), ' ' );
+  let theLayers                         = myTUI.addLayerFrame ( vec!( theText, thePopup ) );
+  let theScreen
+  = myTUI.addParentFrame
+    (
+      Tiling::Grid,
+      vec!
+      (
+        Frame::newInstance ( theLayers,    0, 0, 4, 1, 4, 1, 4, 1, 0, 0, 1, 1 ),
+        Frame::newInstance ( theStatusBar, 0, 1, 4, 1, 4, 1, 4, 1, 0, 1, 1, 1 ),
+      ),
+      vec!( 0, 4 ),
+      vec!( 0, 1, 2 ),
+      vec!( 1 ),
+      vec!( 1, 1 ),
+      FrameID::None,
+    );
+  myTUI.turnOnDisplay ( myDisplay, theScreen, "Test".to_string() ).unwrap();
+  myTUI.render();
+
+  let display                           = myTUI.accessDisplay ( myDisplay ).unwrap();
+  assert_eq!( display.framesAt ( 1, 0 ), vec!( thePopup, theLayers, theScreen ) );
+  assert_eq!( display.framesAt ( 3, 1 ), vec!( theStatusBar, theScreen ) );
+  assert!( display.framesAt ( 4, 0 ).is_empty() );
+}
+
+#[test]
+fn frameFocusIsReported()
+{
+  let mut myTUI                         = Ferrocene::new();
+  let myDisplay                         = myTUI.addMemoryDisplay ( DisplayFlag::None, 0, 0, 3, 1, 0 );
+  let theText                           = myTUI.addTextFrame ( FrameFlag::None, 0, 0, vec!(), ' ' );
+  let theEditor                         = myTUI.addEditorFrame ( FrameFlag::None, 0, 0, vec!(), ' ' );
+  myTUI.turnOnDisplay ( myDisplay, theText, "Test".to_string() ).unwrap();
+  assert!( myTUI.waitEvent ( Some ( Duration::from_millis ( 10 ) ) ).is_none() );
+
+  myTUI.focusFrame ( myDisplay, theEditor ).unwrap();
+  let event                             = myTUI.waitEvent ( Some ( Duration::from_millis ( 10 ) ) ).unwrap();
+  assert!( matches!( event.event, EventType::FrameFocusLost   ) );
+  assert_eq!( event.frame, theText );
+  let event                             = myTUI.waitEvent ( Some ( Duration::from_millis ( 10 ) ) ).unwrap();
+  assert!( matches!( event.event, EventType::FrameFocusGained ) );
+  assert_eq!( event.frame, theEditor );
+  assert!( myTUI.waitEvent ( Some ( Duration::from_millis ( 10 ) ) ).is_none() );
+
+  //reported as well, while the display is turned off.
+  myTUI.turnOffDisplay ( myDisplay ).unwrap();
+  myTUI.focusFrame ( myDisplay, theText ).unwrap();
+  let event                             = myTUI.waitEvent ( Some ( Duration::from_millis ( 10 ) ) ).unwrap();
+  assert!( matches!( event.event, EventType::FrameFocusLost   ) );
+  assert_eq!( event.frame, theEditor );
+  let event                             = myTUI.waitEvent ( Some ( Duration::from_millis ( 10 ) ) ).unwrap();
+  assert!( matches!( event.event, EventType::FrameFocusGained ) );
+  assert_eq!( event.frame, theText );
+  assert!( matches!( myTUI.focusFrame ( myDisplay, FrameID::new ( 9, 1 ) ), Err(Error::InvalidFrame(_)) ) );
+}
@@ -0,0 +1,199 @@
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+
+use ferrocene::
+{
+  Ferrocene,
+  display::
+  {
+    DisplayFlag,
+  },
+  event::
+  {
+    Event,
+    EventType,
+    KeyAction,
+    Modifier,
+    MouseButton,
+    record::
+    {
+      EventRecorder,
+      EventReplay,
+    },
+  },
+  frame::
+  {
+    FrameFlag,
+    FrameID,
+  },
+};
+use std::
+{
+  env,
+  fs,
+  io,
+  path::
+  {
+    PathBuf,
+  },
+  process,
+  time::
+  {
+    Duration,
+    Instant,
+  },
+};
+
+fn temporary
+(
+  name:                                 &str,
+) -> PathBuf
+{
+  env::temp_dir().join ( format!( "ferrocene-{}-{}.events", name, process::id() ) )
+}
+
+fn receive
+(
+  myTUI:                                &mut Ferrocene,
+) -> Vec<Event>
+{
+  let mut listOfEvents                  = vec!();
+  while let Some(event) = myTUI.waitEvent ( Some ( Duration::from_millis ( 20 ) ) )
+  {
+    listOfEvents.push ( event );
+  }
+  listOfEvents
+}
+
+#[test]
+fn eventsAreRecordedAndReplayed()
+{
+  let path                              = temporary ( "replay" );
+  let frame                             = FrameID::new ( 2, 3 );
+  let mut myTUI                         = Ferrocene::new();
+  myTUI.recorder                        = Some(EventRecorder::create ( &path ).unwrap());
+  let mut click                         = Event::new ( EventType::MouseLeftButtonPressed, ferrocene::DisplayID::new ( 1, 1 ), frame, 4, 2, MouseButton::LeftDown );
+  click.localX                          = -1;
+  click.modifiers                       = Modifier::Ctrl;
+  let mut release                       = Event::new ( EventType::Character('ä'), ferrocene::DisplayID::None, frame, 0, 0, MouseButton::None );
+  release.action                        = KeyAction::Release;
+  let listOfEvents                      = vec!
+  [
+    click,
+    release,
+    Event::new ( EventType::Paste(String::from ( "two words\n" )), ferrocene::DisplayID::None, frame, 0, 0, MouseButton::None ),
+    Event::new ( EventType::Warning("not recorded"),              ferrocene::DisplayID::None, frame, 0, 0, MouseButton::None ),
+    Event::new ( EventType::Resize { width: 80, height: 24 },     ferrocene::DisplayID::None, frame, 0, 0, MouseButton::None ),
+    Event::new ( EventType::DragEnd { source: frame },            ferrocene::DisplayID::None, frame, 0, 0, MouseButton::None ),
+  ];
+  for event                             in listOfEvents
+  {
+    myTUI.sendChannel.send ( event ).unwrap();
+  }
+  assert_eq!( receive ( &mut myTUI ).len(), 6 );
+  myTUI.recorder                        = None;
+
+  let replay                            = EventReplay::open ( &path ).unwrap();
+  fs::remove_file ( &path ).unwrap();
+  assert_eq!( replay.len(), 4 );
+  let mut myTUI                         = Ferrocene::new();
+  replay.replay ( &myTUI.sendChannel, f64::INFINITY );
+  let listOfEvents                      = receive ( &mut myTUI );
+  assert_eq!( listOfEvents.len(), 4 );
+  assert!( matches!( listOfEvents [ 0 ].event, EventType::MouseLeftButtonPressed ) );
+  assert_eq!( listOfEvents [ 0 ].display, ferrocene::DisplayID::new ( 1, 1 ) );
+  assert_eq!( listOfEvents [ 0 ].frame, frame );
+  assert_eq!( ( listOfEvents [ 0 ].cursorX, listOfEvents [ 0 ].cursorY ), ( 4, 2 ) );
+  assert_eq!( ( listOfEvents [ 0 ].localX, listOfEvents [ 0 ].localY ), ( -1, 0 ) );
+  assert_eq!( listOfEvents [ 0 ].mouse, MouseButton::LeftDown );
+  assert_eq!( listOfEvents [ 0 ].modifiers, Modifier::Ctrl );
+  assert!( matches!( listOfEvents [ 1 ].event, EventType::Character('ä') ) );
+  assert_eq!( listOfEvents [ 1 ].action, KeyAction::Release );
+  assert!( matches!( listOfEvents [ 2 ].event, EventType::Paste(ref text) if text == "two words\n" ) );
+  assert!( matches!( listOfEvents [ 3 ].event, EventType::DragEnd { source } if source == frame ) );
+}
+
+//the application moves the focus again, when it replays the keys, that made it do so.
+fn focusOnTab
+(
+  myTUI:                                &mut Ferrocene,
+  myDisplay:                            ferrocene::DisplayID,
+  theEditor:                            FrameID,
+) -> Vec<Event>
+{
+  let mut listOfEvents                  = vec!();
+  while let Some(event) = myTUI.waitEvent ( Some ( Duration::from_millis ( 20 ) ) )
+  {
+    if matches!( event.event, EventType::Tab )
+    {
+      myTUI.focusFrame ( myDisplay, theEditor ).unwrap();
+    }
+    listOfEvents.push ( event );
+  }
+  listOfEvents
+}
+
+#[test]
+fn focusMovesAreNotReplayed()
+{
+  let path                              = temporary ( "focus" );
+  let mut listOfSessions                = vec!();
+  for replaying                         in [ false, true ]
+  {
+    let mut myTUI                       = Ferrocene::new();
+    let myDisplay                       = myTUI.addMemoryDisplay ( DisplayFlag::None, 0, 0, 3, 1, 0 );
+    let theText                         = myTUI.addTextFrame ( FrameFlag::None, 0, 0, vec!(), ' ' );
+    let theEditor                       = myTUI.addEditorFrame ( FrameFlag::None, 0, 0, vec!(), ' ' );
+    myTUI.turnOnDisplay ( myDisplay, theText, "Test".to_string() ).unwrap();
+    if !replaying
+    {
+      myTUI.recorder                    = Some(EventRecorder::create ( &path ).unwrap());
+      for event                         in [ EventType::Character('a'), EventType::Tab, EventType::Character('b') ]
+      {
+        myTUI.sendChannel.send ( Event::new ( event, myDisplay, FrameID::None, 0, 0, MouseButton::None ) ).unwrap();
+      }
+    }
+    else
+    {
+      let replay                        = EventReplay::open ( &path ).unwrap();
+      fs::remove_file ( &path ).unwrap();
+      assert_eq!( replay.len(), 3 );
+      replay.replay ( &myTUI.sendChannel, f64::INFINITY );
+    }
+    listOfSessions.push ( focusOnTab ( &mut myTUI, myDisplay, theEditor ) );
+  }
+  //the same keys and the same moves of the focus, each of them once.
+  for listOfEvents                      in listOfSessions.iter()
+  {
+    assert_eq!( listOfEvents.len(), 5 );
+    assert!( matches!( listOfEvents [ 0 ].event, EventType::Character('a')   ) );
+    assert!( matches!( listOfEvents [ 1 ].event, EventType::Tab              ) );
+    assert!( matches!( listOfEvents [ 2 ].event, EventType::Character('b')   ) );
+    assert!( matches!( listOfEvents [ 3 ].event, EventType::FrameFocusLost   ) );
+    assert!( matches!( listOfEvents [ 4 ].event, EventType::FrameFocusGained ) );
+  }
+}
+
+#[test]
+fn replayIsAccelerated()
+{
+  let recording                         = "0 0.0 0.0 0 0 0 0 0 0 Press Character 97\n\
+                                           400000 0.0 0.0 0 0 0 0 0 0 Press Escape\n";
+  let ( sender, receiver )              = Event::openChannel();
+  let start                             = Instant::now();
+  let replay                            = EventReplay::read ( recording.as_bytes() ).unwrap().spawn ( sender, 4.0 );
+  assert!( matches!( receiver.recv().unwrap().event, EventType::Character('a')   ) );
+  assert!( matches!( receiver.recv().unwrap().event, EventType::Escape ) );
+  let elapsed                           = start.elapsed();
+  assert!( elapsed >= Duration::from_millis ( 100 ) );
+  assert!( elapsed <  Duration::from_millis ( 400 ) );
+  replay.join().unwrap();
+}
+
+#[test]
+fn invalidLinesAreRejected()
+{
+  let error                             = EventReplay::read ( "0 0.0 0.0 0 0 0 0 0 0 Press Escape\n1 0.0 0.0 0 0 0 0 0 0 Press Foo\n".as_bytes() ).err().unwrap();
+  assert_eq!( error.kind(), io::ErrorKind::InvalidData );
+  assert_eq!( error.to_string(), "invalid event in line 2" );
+}
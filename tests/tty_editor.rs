@@ -18,6 +18,7 @@ use ferrocene::
   {
     Frame,
     FrameFlag,
+    FrameID,
     PixelEncoding,
     Tiling,
     style::
@@ -51,7 +52,7 @@ fn main()
       0,                                0,
       Box::new(io::stdin()),            Box::new(io::stdout()),
       10_000_000
-    );
+    ).unwrap();
   let theStatusBar
   = myTUI.addStatusFrame
     (
@@ -175,7 +176,7 @@ fn main()
       vec!(0, height as isize - 1, height as isize),
       vec!(1),
       vec!(1, 1),
-      0
+      FrameID::None
     );
   
   println!("turnOnDisplay");
@@ -186,37 +187,36 @@ fn main()
     "Hello Foobar".to_string()
   ).unwrap();
 
-  'mainLoop:
-    loop
+  myTUI.run
+  (
+    | myTUI, event |
     {
-      for event                         in                                      &myTUI.recvChannel.try_recv()
+      match event.event
       {
-        match event.event
+        EventType::Error(ref message)   =>
         {
-          EventType::Error(ref message) =>
-          {
-            println!("FAIL: {}", message);
-          },
-          EventType::Character(character) =>
+          println!("FAIL: {}", message);
+        },
+        EventType::Character(character) =>
+        {
+          match character
           {
-            match character
+            'q' =>
+            {
+              return false;
+            },
+            'a' =>
             {
-              'q' =>
-              {
-                break 'mainLoop;
-              },
-              'a' =>
-              {
-                myTUI.setDisplayTitle(myTerminal, "trolololololo".to_string());
-              },
-              _ => {}
-            }
-          },
-          _ => {}
-        }
+              myTUI.setDisplayTitle(myTerminal, "trolololololo".to_string()).unwrap();
+            },
+            _ => {}
+          }
+        },
+        _ => {}
       }
-      myTUI.render();
+      true
     }
+  );
 
   println!("turnOffDisplay");
   myTUI.turnOffDisplay
@@ -234,37 +234,36 @@ fn main()
     "Hello World".to_string()
   ).unwrap();
 
-  'mainLoop1:
-    loop
+  myTUI.run
+  (
+    | myTUI, event |
     {
-      for event                         in                                      &myTUI.recvChannel.try_recv()
+      match event.event
       {
-        match event.event
+        EventType::Error(ref message)   =>
         {
-          EventType::Error(ref message) =>
-          {
-            println!("FAIL: {}", message);
-          },
-          EventType::Character(character) =>
+          println!("FAIL: {}", message);
+        },
+        EventType::Character(character) =>
+        {
+          match character
           {
-            match character
+            'q' =>
+            {
+              return false;
+            },
+            'a' =>
             {
-              'q' =>
-              {
-                break 'mainLoop1;
-              },
-              'a' =>
-              {
-                myTUI.setDisplayTitle(myTerminal, "trolololololo".to_string());
-              },
-              _ => {}
-            }
-          },
-          _ => {}
-        }
+              myTUI.setDisplayTitle(myTerminal, "trolololololo".to_string()).unwrap();
+            },
+            _ => {}
+          }
+        },
+        _ => {}
       }
-      myTUI.render();
+      true
     }
+  );
   myTUI.turnOffDisplay
   (
     myTerminal,
@@ -0,0 +1,65 @@
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+
+#![cfg(feature = "display-tty")]
+
+//a test binary of its own, because the cpu time of the process is only that of the listener, while no other test runs.
+
+mod common;
+
+use ferrocene::
+{
+  display::
+  {
+    DisplayFlag,
+  },
+  event::
+  {
+    EventType,
+  },
+};
+use common::
+{
+  openDisplay,
+};
+use std::
+{
+  io::
+  {
+    Write,
+  },
+  thread,
+  time,
+};
+
+//cpu time used by the whole test process so far.
+fn processTime
+(
+) -> time::Duration
+{
+  unsafe
+  {
+    let mut timespec: libc::timespec    = std::mem::zeroed();
+    assert_eq!( libc::clock_gettime ( libc::CLOCK_PROCESS_CPUTIME_ID, &mut timespec ), 0 );
+    time::Duration::new ( timespec.tv_sec as u64, timespec.tv_nsec as u32 )
+  }
+}
+
+#[test]
+fn idleListenerSleeps()
+{
+  let ( mut master, mut myTUI, myTerminal, _ )
+                                        = openDisplay ( DisplayFlag::None, &[] );
+
+  let start                             = processTime();
+  thread::sleep ( time::Duration::from_millis ( 200 ) );
+  assert!( processTime() - start < time::Duration::from_millis ( 50 ) );
+
+  master.write_all ( b"x" ).unwrap();
+  let event                             = myTUI.waitEvent ( Some ( time::Duration::from_secs ( 1 ) ) ).unwrap();
+  assert!( matches!( event.event, EventType::Character('x') ) );
+
+  let start                             = time::Instant::now();
+  myTUI.turnOffDisplay ( myTerminal ).unwrap();
+  assert!( start.elapsed() < time::Duration::from_millis ( 100 ) );
+}
@@ -0,0 +1,504 @@
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+
+#![cfg(feature = "display-tty")]
+
+mod common;
+
+use ferrocene::
+{
+  display::
+  {
+    DisplayFlag,
+  },
+  event::
+  {
+    EventType,
+    KeyAction,
+    Modifier,
+    MouseButton,
+    gesture::
+    {
+      GestureRecognizer,
+    },
+  },
+  frame::
+  {
+    Frame,
+    FrameFlag,
+  },
+};
+use common::
+{
+  addDisplay,
+  openDisplay,
+  readTerminal,
+};
+use std::
+{
+  io::
+  {
+    Write,
+  },
+  os::
+  {
+    unix::
+    {
+      io::
+      {
+        AsRawFd,
+      },
+    },
+  },
+  thread,
+  time,
+};
+
+#[test]
+fn onlyChangedCellsAreSent()
+{
+  let ( mut master, mut myTUI, myTerminal, theText )
+                                        = openDisplay ( DisplayFlag::None, &[ "abc" ] );
+  let display                           = myTUI.accessDisplay ( myTerminal ).unwrap();
+  assert_eq!( ( display.sizeX, display.sizeY ), ( 8, 2 ) );
+
+  myTUI.render();
+  let first                             = readTerminal ( &mut master );
+  assert!( first.contains ( "abc" ) );
+
+  if let Frame::Text ( ref mut frame ) = myTUI.accessFrame ( theText ).unwrap()
+  {
+    frame.lines                         = vec!( "abd".to_string() );
+  }
+  myTUI.accessDisplay ( myTerminal ).unwrap().flags
+                                        |= DisplayFlag::NeedRefresh;
+  myTUI.render();
+  let second                            = readTerminal ( &mut master );
+  assert_eq!( second, "\x1b[1;3H\x1b[0;39;49md\x1b[0m" );
+
+  myTUI.render();
+  assert_eq!( readTerminal ( &mut master ), "" );
+
+  myTUI.turnOffDisplay ( myTerminal ).unwrap();
+}
+
+#[test]
+fn wideCharactersAreFollowedAbsolutely()
+{
+  let ( mut master, mut myTUI, myTerminal, theText )
+                                        = openDisplay ( DisplayFlag::None, &[ "a日bc" ] );
+  myTUI.render();
+  readTerminal ( &mut master );
+
+  //the wide character moves the real cursor by two columns, so the next cell needs its own position.
+  if let Frame::Text ( ref mut frame ) = myTUI.accessFrame ( theText ).unwrap()
+  {
+    frame.lines                         = vec!( "a本dc".to_string() );
+  }
+  myTUI.accessDisplay ( myTerminal ).unwrap().flags
+                                        |= DisplayFlag::NeedRefresh;
+  myTUI.render();
+  assert_eq!( readTerminal ( &mut master ), "\x1b[1;2H\x1b[0;39;49m本\x1b[1;3Hd\x1b[0m" );
+
+  myTUI.turnOffDisplay ( myTerminal ).unwrap();
+}
+
+#[test]
+fn resizeIsReported()
+{
+  let ( mut master, mut myTUI, myTerminal, _ )
+                                        = openDisplay ( DisplayFlag::None, &[ "abcdefghij" ] );
+  myTUI.render();
+  readTerminal ( &mut master );
+
+  //this process does not own the pseudo terminal, so the kernel would not send the signal itself.
+  unsafe
+  {
+    let winsize
+    = libc::winsize
+      {
+        ws_row:                         3,
+        ws_col:                         10,
+        ws_xpixel:                      0,
+        ws_ypixel:                      0,
+      };
+    assert_eq!( libc::ioctl ( master.as_raw_fd(), libc::TIOCSWINSZ, &winsize ), 0 );
+    libc::raise ( libc::SIGWINCH );
+  }
+  let event                             = myTUI.waitEvent ( Some ( time::Duration::from_secs ( 1 ) ) ).unwrap();
+  assert!( matches!( event.event, EventType::Resize { width: 10, height: 3 } ) );
+  assert_eq!( event.display, myTerminal );
+
+  myTUI.render();
+  let display                           = myTUI.accessDisplay ( myTerminal ).unwrap();
+  assert_eq!( ( display.sizeX, display.sizeY ), ( 10, 3 ) );
+  let redrawn                           = readTerminal ( &mut master );
+  assert!( redrawn.starts_with ( "\x1b[2J" ) );
+  assert!( redrawn.contains ( "abcdefghij" ) );
+
+  myTUI.turnOffDisplay ( myTerminal ).unwrap();
+}
+
+#[test]
+fn screenIsRestored()
+{
+  for &( flags, expectOn, expectOff ) in
+  [
+    ( DisplayFlag::None,          "\x1b[?1049h", "\x1b[?1004l\x1b[?2004l\x1b[?1006l\x1b[?1003l\x1b[?25h\x1b[?1049l" ),
+    ( DisplayFlag::ResetTerminal, "\x1b[2J",     "\x1bc"                                      ),
+  ].iter()
+  {
+    let ( mut master, mut myTUI, myTerminal )
+                                        = addDisplay ( flags );
+    let theText                         = myTUI.addTextFrame ( FrameFlag::None, 0, 0, vec!(), ' ' );
+    myTUI.turnOnDisplay ( myTerminal, theText, "Test".to_string() ).unwrap();
+    let turnedOn                        = readTerminal ( &mut master );
+    assert!( turnedOn.contains ( expectOn ) );
+    assert_eq!( turnedOn.contains ( "\x1b[?1049h" ), flags == DisplayFlag::None );
+
+    myTUI.turnOffDisplay ( myTerminal ).unwrap();
+    assert_eq!( readTerminal ( &mut master ), expectOff );
+  }
+}
+
+#[test]
+fn keysAreDecoded()
+{
+  let ( mut master, mut myTUI, myTerminal, _ )
+                                        = openDisplay ( DisplayFlag::None, &[] );
+
+  master.write_all ( b"\x1b[A\x1b[1;5C\x1bOP\x1b[15~\x1b[3;3~\x1b[Z\x01\r\t\x7f" ).unwrap();
+  let mut listOfEvents                  = vec!();
+  while listOfEvents.len() < 10
+  {
+    listOfEvents.push ( myTUI.waitEvent ( Some ( time::Duration::from_secs ( 1 ) ) ).unwrap() );
+  }
+  assert!( matches!( listOfEvents [ 0 ].event, EventType::Up          ) );
+  assert!( matches!( listOfEvents [ 1 ].event, EventType::Right       ) );
+  assert_eq!( listOfEvents [ 1 ].modifiers, Modifier::Ctrl );
+  assert!( matches!( listOfEvents [ 2 ].event, EventType::Function(1) ) );
+  assert!( matches!( listOfEvents [ 3 ].event, EventType::Function(5) ) );
+  assert!( matches!( listOfEvents [ 4 ].event, EventType::Delete      ) );
+  assert_eq!( listOfEvents [ 4 ].modifiers, Modifier::Alt );
+  assert!( matches!( listOfEvents [ 5 ].event, EventType::Tab         ) );
+  assert_eq!( listOfEvents [ 5 ].modifiers, Modifier::Shift );
+  assert!( matches!( listOfEvents [ 6 ].event, EventType::Ctrl('a')   ) );
+  assert!( matches!( listOfEvents [ 7 ].event, EventType::Return      ) );
+  assert!( matches!( listOfEvents [ 8 ].event, EventType::Tab         ) );
+  assert!( matches!( listOfEvents [ 9 ].event, EventType::Backspace   ) );
+  assert_eq!( listOfEvents [ 9 ].modifiers, Modifier::None );
+
+  myTUI.turnOffDisplay ( myTerminal ).unwrap();
+}
+
+#[test]
+fn utf8IsAssembled()
+{
+  let ( mut master, mut myTUI, myTerminal, _ )
+                                        = openDisplay ( DisplayFlag::None, &[] );
+
+  //the euro sign is split across two reads.
+  master.write_all ( "ä".as_bytes() ).unwrap();
+  master.write_all ( b"\xe2" ).unwrap();
+  thread::sleep ( time::Duration::from_millis ( 50 ) );
+  master.write_all ( b"\x82\xac\xff\xe2(" ).unwrap();
+  let mut listOfEvents                  = vec!();
+  while listOfEvents.len() < 5
+  {
+    listOfEvents.push ( myTUI.waitEvent ( Some ( time::Duration::from_secs ( 1 ) ) ).unwrap() );
+  }
+  assert!( matches!( listOfEvents [ 0 ].event, EventType::Character('ä') ) );
+  assert!( matches!( listOfEvents [ 1 ].event, EventType::Character('€') ) );
+  assert!( matches!( listOfEvents [ 2 ].event, EventType::Warning(_)     ) );
+  assert!( matches!( listOfEvents [ 3 ].event, EventType::Warning(_)     ) );
+  assert!( matches!( listOfEvents [ 4 ].event, EventType::Character('(') ) );
+
+  myTUI.turnOffDisplay ( myTerminal ).unwrap();
+}
+
+#[test]
+fn escapeTimesOut()
+{
+  let ( mut master, mut myTUI, myTerminal, _ )
+                                        = openDisplay ( DisplayFlag::None, &[] );
+  myTUI.setEscapeTimeout ( myTerminal, time::Duration::from_millis ( 100 ) ).unwrap();
+
+  //a lone escape key is only reported after the timeout.
+  let start                             = time::Instant::now();
+  master.write_all ( b"\x1b" ).unwrap();
+  let event                             = myTUI.waitEvent ( Some ( time::Duration::from_secs ( 1 ) ) ).unwrap();
+  assert!( matches!( event.event, EventType::Escape ) );
+  assert!( start.elapsed() >= time::Duration::from_millis ( 100 ) );
+
+  master.write_all ( b"\x1bx" ).unwrap();
+  let event                             = myTUI.waitEvent ( Some ( time::Duration::from_secs ( 1 ) ) ).unwrap();
+  assert!( matches!( event.event, EventType::Alt('x') ) );
+  assert_eq!( event.modifiers, Modifier::Alt );
+
+  //a sequence split in two within the timeout is still one key.
+  master.write_all ( b"\x1b" ).unwrap();
+  thread::sleep ( time::Duration::from_millis ( 20 ) );
+  master.write_all ( b"[B" ).unwrap();
+  let event                             = myTUI.waitEvent ( Some ( time::Duration::from_secs ( 1 ) ) ).unwrap();
+  assert!( matches!( event.event, EventType::Down ) );
+  assert!( myTUI.waitEvent ( Some ( time::Duration::from_millis ( 200 ) ) ).is_none() );
+
+  myTUI.turnOffDisplay ( myTerminal ).unwrap();
+}
+
+#[test]
+fn pasteIsOneEvent()
+{
+  let ( mut master, mut myTUI, myTerminal )
+                                        = addDisplay ( DisplayFlag::None );
+  let theText                           = myTUI.addTextFrame ( FrameFlag::None, 0, 0, vec!(), ' ' );
+  myTUI.turnOnDisplay ( myTerminal, theText, "Test".to_string() ).unwrap();
+  assert!( readTerminal ( &mut master ).contains ( "\x1b[?2004h" ) );
+
+  master.write_all ( b"\x1b[200~one\r\x1b[Atw" ).unwrap();
+  thread::sleep ( time::Duration::from_millis ( 20 ) );
+  master.write_all ( "ö\x1b[201~x".as_bytes() ).unwrap();
+  let event                             = myTUI.waitEvent ( Some ( time::Duration::from_secs ( 1 ) ) ).unwrap();
+  match event.event
+  {
+    EventType::Paste(ref text)          => assert_eq!( text, "one\r\x1b[Atwö" ),
+    _                                   => panic!("not pasted"),
+  }
+  let event                             = myTUI.waitEvent ( Some ( time::Duration::from_secs ( 1 ) ) ).unwrap();
+  assert!( matches!( event.event, EventType::Character('x') ) );
+
+  myTUI.turnOffDisplay ( myTerminal ).unwrap();
+}
+
+#[test]
+fn slowPasteIsOneEvent()
+{
+  let ( mut master, mut myTUI, myTerminal, _ )
+                                        = openDisplay ( DisplayFlag::None, &[] );
+
+  //like over a slow connection, each part comes later than the escape timeout.
+  master.write_all ( b"\x1b[200~" ).unwrap();
+  for index                             in 0 .. 10
+  {
+    thread::sleep ( time::Duration::from_millis ( 60 ) );
+    master.write_all ( format!( "{} ", index ).as_bytes() ).unwrap();
+  }
+  master.write_all ( b"\x1b[201~" ).unwrap();
+  let event                             = myTUI.waitEvent ( Some ( time::Duration::from_secs ( 1 ) ) ).unwrap();
+  match event.event
+  {
+    EventType::Paste(ref text)          => assert_eq!( text, "0 1 2 3 4 5 6 7 8 9 " ),
+    _                                   => panic!("not pasted"),
+  }
+  assert!( myTUI.waitEvent ( Some ( time::Duration::from_millis ( 100 ) ) ).is_none() );
+
+  //without its end, the text is delivered after a long pause, but later bytes are still pasted.
+  let start                             = time::Instant::now();
+  master.write_all ( b"\x1b[200~lost" ).unwrap();
+  let event                             = myTUI.waitEvent ( Some ( time::Duration::from_secs ( 3 ) ) ).unwrap();
+  assert!( matches!( event.event, EventType::Paste(ref text) if text == "lost" ) );
+  assert!( start.elapsed() >= time::Duration::from_secs ( 1 ) );
+  master.write_all ( b"q\x1b[201~" ).unwrap();
+  let event                             = myTUI.waitEvent ( Some ( time::Duration::from_secs ( 1 ) ) ).unwrap();
+  assert!( matches!( event.event, EventType::Paste(ref text) if text == "q" ) );
+
+  myTUI.turnOffDisplay ( myTerminal ).unwrap();
+}
+
+//answers the queries sent while turning on, like a terminal with or without the kitty keyboard protocol.
+fn answerQueries
+(
+  master:                               &std::fs::File,
+  answer:                               &'static str,
+) -> thread::JoinHandle<()>
+{
+  let mut master                        = master.try_clone().unwrap();
+  thread::spawn
+  (
+    move ||
+    {
+      let mut output                    = String::new();
+      while !output.contains ( "\x1b[?u\x1b[c" )
+      {
+        output.push_str ( &readTerminal ( &mut master ) );
+      }
+      master.write_all ( answer.as_bytes() ).unwrap();
+    }
+  )
+}
+
+#[test]
+fn kittyKeyboardIsNegotiated()
+{
+  let ( mut master, mut myTUI, myTerminal )
+                                        = addDisplay ( DisplayFlag::KittyKeyboard );
+  let theText                           = myTUI.addTextFrame ( FrameFlag::None, 0, 0, vec!(), ' ' );
+  let answering                         = answerQueries ( &master, "\x1b[?0u\x1b[?62;22c" );
+  myTUI.turnOnDisplay ( myTerminal, theText, "Test".to_string() ).unwrap();
+  answering.join().unwrap();
+  assert!( readTerminal ( &mut master ).contains ( "\x1b[>3u" ) );
+
+  //Ctrl-I, Tab, releasing a, repeating Ctrl-Up, escape and F13.
+  master.write_all ( b"\x1b[105;5u\t\x1b[97;1:3u\x1b[1;5:2A\x1b[27u\x1b[57376u" ).unwrap();
+  let mut listOfEvents                  = vec!();
+  while listOfEvents.len() < 6
+  {
+    listOfEvents.push ( myTUI.waitEvent ( Some ( time::Duration::from_secs ( 1 ) ) ).unwrap() );
+  }
+  assert!( matches!( listOfEvents [ 0 ].event, EventType::Ctrl('i')      ) );
+  assert_eq!( listOfEvents [ 0 ].modifiers, Modifier::Ctrl );
+  assert!( matches!( listOfEvents [ 1 ].event, EventType::Tab            ) );
+  assert!( matches!( listOfEvents [ 2 ].event, EventType::Character('a') ) );
+  assert_eq!( listOfEvents [ 2 ].action, KeyAction::Release );
+  assert!( matches!( listOfEvents [ 3 ].event, EventType::Up             ) );
+  assert_eq!( listOfEvents [ 3 ].modifiers, Modifier::Ctrl );
+  assert_eq!( listOfEvents [ 3 ].action, KeyAction::Repeat );
+  assert!( matches!( listOfEvents [ 4 ].event, EventType::Escape         ) );
+  assert_eq!( listOfEvents [ 4 ].action, KeyAction::Press );
+  assert!( matches!( listOfEvents [ 5 ].event, EventType::Function(13)   ) );
+
+  myTUI.turnOffDisplay ( myTerminal ).unwrap();
+  assert!( readTerminal ( &mut master ).starts_with ( "\x1b[<u" ) );
+}
+
+#[test]
+fn legacyKeyboardIsKept()
+{
+  let ( mut master, mut myTUI, myTerminal )
+                                        = addDisplay ( DisplayFlag::KittyKeyboard );
+  let theText                           = myTUI.addTextFrame ( FrameFlag::None, 0, 0, vec!(), ' ' );
+  let answering                         = answerQueries ( &master, "\x1b[?62;22c" );
+  myTUI.turnOnDisplay ( myTerminal, theText, "Test".to_string() ).unwrap();
+  answering.join().unwrap();
+  assert!( !readTerminal ( &mut master ).contains ( "\x1b[>" ) );
+
+  master.write_all ( b"\t" ).unwrap();
+  let event                             = myTUI.waitEvent ( Some ( time::Duration::from_secs ( 1 ) ) ).unwrap();
+  assert!( matches!( event.event, EventType::Tab ) );
+
+  myTUI.turnOffDisplay ( myTerminal ).unwrap();
+  assert!( !readTerminal ( &mut master ).contains ( "\x1b[<u" ) );
+}
+
+#[test]
+fn mouseButtonsAndModifiers()
+{
+  let ( mut master, mut myTUI, myTerminal, _ )
+                                        = openDisplay ( DisplayFlag::None, &[] );
+
+  //Ctrl-click, Shift-Alt-wheel up, wheel right, back pressed and released.
+  master.write_all ( b"\x1b[<16;1;1M\x1b[<76;2;1M\x1b[<67;1;2M\x1b[<128;3;2M\x1b[<128;3;2m" ).unwrap();
+  let mut listOfEvents                  = vec!();
+  while listOfEvents.len() < 5
+  {
+    listOfEvents.push ( myTUI.waitEvent ( Some ( time::Duration::from_secs ( 1 ) ) ).unwrap() );
+  }
+  assert!( matches!( listOfEvents [ 0 ].event, EventType::MouseLeftButtonPressed  ) );
+  assert_eq!( listOfEvents [ 0 ].modifiers, Modifier::Ctrl );
+  assert!( matches!( listOfEvents [ 1 ].event, EventType::MouseWheelUp            ) );
+  assert_eq!( listOfEvents [ 1 ].modifiers, Modifier::Shift | Modifier::Alt );
+  assert!( matches!( listOfEvents [ 2 ].event, EventType::MouseWheelRight         ) );
+  assert_eq!( listOfEvents [ 2 ].modifiers, Modifier::None );
+  assert!( matches!( listOfEvents [ 3 ].event, EventType::MouseBackButtonPressed  ) );
+  assert!( listOfEvents [ 3 ].mouse.contains ( MouseButton::BackDown ) );
+  assert!( matches!( listOfEvents [ 4 ].event, EventType::MouseBackButtonReleased ) );
+  assert!( !listOfEvents [ 4 ].mouse.contains ( MouseButton::BackDown ) );
+
+  myTUI.turnOffDisplay ( myTerminal ).unwrap();
+}
+
+#[test]
+fn mouseIsLocalToFrame()
+{
+  let ( mut master, mut myTUI, myTerminal )
+                                        = addDisplay ( DisplayFlag::None );
+  let theEditor                         = myTUI.addEditorFrame ( FrameFlag::None, 1, 2, vec!(), ' ' );
+  myTUI.turnOnDisplay ( myTerminal, theEditor, "Test".to_string() ).unwrap();
+  myTUI.render();
+  readTerminal ( &mut master );
+
+  //third column of the second row, but scrolled by one column and two lines.
+  master.write_all ( b"\x1b[<0;3;2M" ).unwrap();
+  let event                             = myTUI.waitEvent ( Some ( time::Duration::from_secs ( 1 ) ) ).unwrap();
+  assert!( matches!( event.event, EventType::MouseLeftButtonPressed ) );
+  assert_eq!( event.frame, theEditor );
+  assert_eq!( ( event.cursorX, event.cursorY ), ( 2, 1 ) );
+  assert_eq!( ( event.localX,  event.localY  ), ( 3, 3 ) );
+
+  myTUI.turnOffDisplay ( myTerminal ).unwrap();
+}
+
+#[test]
+fn displayFocusIsReported()
+{
+  let ( mut master, mut myTUI, myTerminal )
+                                        = addDisplay ( DisplayFlag::None );
+  let theText                           = myTUI.addTextFrame ( FrameFlag::None, 0, 0, vec!(), ' ' );
+  myTUI.turnOnDisplay ( myTerminal, theText, "Test".to_string() ).unwrap();
+  assert!( readTerminal ( &mut master ).contains ( "\x1b[?1004h" ) );
+
+  master.write_all ( b"\x1b[O\x1b[I" ).unwrap();
+  let event                             = myTUI.waitEvent ( Some ( time::Duration::from_secs ( 1 ) ) ).unwrap();
+  assert!( matches!( event.event, EventType::DisplayFocusLost(display)   if display == myTerminal ) );
+  let event                             = myTUI.waitEvent ( Some ( time::Duration::from_secs ( 1 ) ) ).unwrap();
+  assert!( matches!( event.event, EventType::DisplayFocusGained(display) if display == myTerminal ) );
+
+  myTUI.turnOffDisplay ( myTerminal ).unwrap();
+  assert!( readTerminal ( &mut master ).contains ( "\x1b[?1004l" ) );
+}
+
+#[test]
+fn eventsAreStampedAndNumbered()
+{
+  let ( mut master, mut myTUI, myTerminal, theText )
+                                        = openDisplay ( DisplayFlag::None, &[] );
+
+  let start                             = time::Instant::now();
+  master.write_all ( b"ab" ).unwrap();
+  let first                             = myTUI.waitEvent ( Some ( time::Duration::from_secs ( 1 ) ) ).unwrap();
+  let second                            = myTUI.waitEvent ( Some ( time::Duration::from_secs ( 1 ) ) ).unwrap();
+  assert!( matches!( second.event, EventType::Character('b') ) );
+  assert!( first.timestamp >= start );
+  //both were decoded from the same bytes.
+  assert_eq!( first.timestamp, second.timestamp );
+  assert_eq!( second.sequence, first.sequence + 1 );
+
+  //the escape key is stamped, when it arrived, not when it stopped being a possible sequence.
+  myTUI.setEscapeTimeout ( myTerminal, time::Duration::from_millis ( 100 ) ).unwrap();
+  master.write_all ( b"\x1b" ).unwrap();
+  let escape                            = myTUI.waitEvent ( Some ( time::Duration::from_secs ( 1 ) ) ).unwrap();
+  assert!( matches!( escape.event, EventType::Escape ) );
+  assert!( escape.timestamp.elapsed() >= time::Duration::from_millis ( 100 ) );
+  assert_eq!( escape.sequence, second.sequence + 1 );
+
+  myTUI.sendChannel.send ( ferrocene::Event::new ( EventType::Character('c'), myTerminal, theText, 0, 0, MouseButton::None ) ).unwrap();
+  let sent                              = myTUI.waitEvent ( Some ( time::Duration::from_secs ( 1 ) ) ).unwrap();
+  assert_eq!( sent.sequence, escape.sequence + 1 );
+
+  myTUI.turnOffDisplay ( myTerminal ).unwrap();
+}
+
+#[test]
+fn gesturesAreNumberedWhenDelivered()
+{
+  let ( mut master, mut myTUI, myTerminal, theText )
+                                        = openDisplay ( DisplayFlag::None, &[] );
+  myTUI.gestures                        = Some(GestureRecognizer::new());
+
+  //the key was decoded together with the click, but is returned after the gesture recognized from the click.
+  master.write_all ( b"\x1b[<0;1;1Ma" ).unwrap();
+  let mut listOfEvents                  = vec!();
+  while listOfEvents.len() < 3
+  {
+    listOfEvents.push ( myTUI.waitEvent ( Some ( time::Duration::from_secs ( 1 ) ) ).unwrap() );
+  }
+  assert!( matches!( listOfEvents [ 0 ].event, EventType::MouseLeftButtonPressed ) );
+  assert!( matches!( listOfEvents [ 1 ].event, EventType::MouseEnter             ) );
+  assert_eq!( listOfEvents [ 1 ].frame, theText );
+  assert!( matches!( listOfEvents [ 2 ].event, EventType::Character('a')         ) );
+  assert_eq!( listOfEvents [ 1 ].sequence, listOfEvents [ 0 ].sequence + 1 );
+  assert_eq!( listOfEvents [ 2 ].sequence, listOfEvents [ 1 ].sequence + 1 );
+  assert_eq!( listOfEvents [ 2 ].timestamp, listOfEvents [ 0 ].timestamp );
+
+  myTUI.turnOffDisplay ( myTerminal ).unwrap();
+}
@@ -0,0 +1,130 @@
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+
+#![cfg(feature = "display-tty")]
+
+mod common;
+
+use ferrocene::
+{
+  Ferrocene,
+  display::
+  {
+    DisplayFlag,
+  },
+};
+use common::
+{
+  openDisplay,
+  readTerminal,
+};
+use std::
+{
+  fs::
+  {
+    File,
+  },
+  os::
+  {
+    unix::
+    {
+      io::
+      {
+        AsRawFd,
+      },
+    },
+  },
+  panic,
+  sync::
+  {
+    atomic::
+    {
+      AtomicBool,
+      Ordering,
+    },
+  },
+};
+
+const restoreScreen:                    &str = "\x1b[?1004l\x1b[?2004l\x1b[?1006l\x1b[?1003l\x1b[?25h\x1b[?1049l";
+
+static terminated:                      AtomicBool = AtomicBool::new ( false );
+
+extern "C" fn onTerminate
+(
+  _signal:                              libc::c_int,
+)
+{
+  terminated.store ( true, Ordering::SeqCst );
+}
+
+fn isRaw
+(
+  master:                               &File,
+) -> bool
+{
+  unsafe
+  {
+    let mut termios: libc::termios      = std::mem::zeroed();
+    assert_eq!( libc::tcgetattr ( master.as_raw_fd(), &mut termios ), 0 );
+    ( termios.c_lflag & libc::ICANON ) == 0
+  }
+}
+
+fn turnedOn
+(
+  flags:                                DisplayFlag,
+) -> ( File, Ferrocene, ferrocene::DisplayID )
+{
+  let ( master, myTUI, myTerminal, _ )  = openDisplay ( flags, &[] );
+  assert!( isRaw ( &master ) );
+  ( master, myTUI, myTerminal )
+}
+
+//all in one test, because the panic hook and the signal handlers restore every terminal of the process.
+#[test]
+fn terminalIsRestored()
+{
+  //dropped without being turned off.
+  let ( mut master, myTUI, _ )          = turnedOn ( DisplayFlag::None );
+  drop ( myTUI );
+  assert_eq!( readTerminal ( &mut master ), restoreScreen );
+  assert!( !isRaw ( &master ) );
+
+  //restored before the panic is reported, and only once.
+  let ( mut master, mut myTUI, myTerminal )
+                                        = turnedOn ( DisplayFlag::RestoreOnPanic );
+  assert!( panic::catch_unwind ( || panic!("crash") ).is_err() );
+  assert_eq!( readTerminal ( &mut master ), restoreScreen );
+  assert!( !isRaw ( &master ) );
+  myTUI.turnOffDisplay ( myTerminal ).unwrap();
+  assert_eq!( readTerminal ( &mut master ), "" );
+
+  //set up again by the next render, if the panic was caught.
+  let ( mut master, mut myTUI, myTerminal )
+                                        = turnedOn ( DisplayFlag::RestoreOnPanic );
+  assert!( panic::catch_unwind ( || panic!("caught") ).is_err() );
+  assert_eq!( readTerminal ( &mut master ), restoreScreen );
+  myTUI.render();
+  assert!( isRaw ( &master ) );
+  assert!( readTerminal ( &mut master ).starts_with ( "\x1b[?1049h\x1b[2J" ) );
+  myTUI.turnOffDisplay ( myTerminal ).unwrap();
+  assert_eq!( readTerminal ( &mut master ), restoreScreen );
+  assert!( !isRaw ( &master ) );
+
+  //restored before the previous handler gets the signal.
+  unsafe
+  {
+    libc::signal ( libc::SIGTERM, onTerminate as extern "C" fn ( libc::c_int ) as libc::sighandler_t );
+  }
+  let ( mut master, mut myTUI, myTerminal )
+                                        = turnedOn ( DisplayFlag::RestoreOnTermination | DisplayFlag::ResetTerminal );
+  unsafe
+  {
+    libc::raise ( libc::SIGTERM );
+  }
+  assert!( terminated.load ( Ordering::SeqCst ) );
+  assert_eq!( readTerminal ( &mut master ), "\x1bc" );
+  assert!( !isRaw ( &master ) );
+  myTUI.turnOffDisplay ( myTerminal ).unwrap();
+  assert_eq!( readTerminal ( &mut master ), "" );
+}
@@ -0,0 +1,67 @@
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+
+#![cfg(feature = "display-tty")]
+
+mod common;
+
+use ferrocene::
+{
+  display::
+  {
+    DisplayFlag,
+  },
+};
+use common::
+{
+  openDisplay,
+};
+use std::
+{
+  io,
+  sync::
+  {
+    atomic::
+    {
+      AtomicBool,
+      Ordering,
+    },
+  },
+};
+
+static resized:                         AtomicBool = AtomicBool::new ( false );
+
+//installed before the display, and clobbers errno like many handlers would.
+extern "C" fn onResize
+(
+  _signal:                              libc::c_int,
+)
+{
+  resized.store ( true, Ordering::SeqCst );
+  unsafe
+  {
+    libc::close ( -1 );
+  }
+}
+
+//a test binary of its own, because the handlers are installed once for the whole process.
+#[test]
+fn previousHandlerIsChained()
+{
+  unsafe
+  {
+    libc::signal ( libc::SIGWINCH, onResize as extern "C" fn ( libc::c_int ) as libc::sighandler_t );
+  }
+  let ( _master, mut myTUI, myTerminal, _ )
+                                        = openDisplay ( DisplayFlag::None, &[] );
+  //errno is set by a failing call, so this works with the errno of any platform.
+  unsafe
+  {
+    libc::chdir ( b"\0".as_ptr() as *const libc::c_char );
+    libc::raise ( libc::SIGWINCH );
+  }
+  assert_eq!( io::Error::last_os_error().raw_os_error(), Some(libc::ENOENT) );
+  assert!( resized.load ( Ordering::SeqCst ) );
+
+  myTUI.turnOffDisplay ( myTerminal ).unwrap();
+}
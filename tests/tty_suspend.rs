@@ -0,0 +1,147 @@
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+
+#![cfg(feature = "display-tty")]
+
+mod common;
+
+use ferrocene::
+{
+  Ferrocene,
+  display::
+  {
+    DisplayFlag,
+  },
+  event::
+  {
+    EventType,
+  },
+  frame::
+  {
+    FrameFlag,
+  },
+};
+use common::
+{
+  openTerminal,
+  readTerminal,
+};
+use std::
+{
+  io::
+  {
+    Write,
+  },
+  panic,
+  thread,
+  time,
+};
+
+//runs in its own process group, so stopping it does not stop the test as well.
+fn suspendedChild
+(
+  flags:                                DisplayFlag,
+  input:                                std::fs::File,
+  output:                               std::fs::File,
+) -> bool
+{
+  let mut myTUI                         = Ferrocene::new();
+  let ( _, _, myTerminal )
+  = myTUI.addTTYDisplay
+    (
+      DisplayFlag::SuspendOnCtrlZ | flags,
+      0,                                0,
+      0,                                0,
+      Box::new ( input ),               Box::new ( output ),
+      0,
+    ).unwrap();
+  let theText                           = myTUI.addTextFrame ( FrameFlag::None, 0, 0, vec!( "abc".to_string() ), ' ' );
+  myTUI.turnOnDisplay ( myTerminal, theText, "Test".to_string() ).unwrap();
+  myTUI.render();
+  let mut listOfEvents                  = vec!();
+  while let Some(event) = myTUI.waitEvent ( Some ( time::Duration::from_secs ( 5 ) ) )
+  {
+    match event.event
+    {
+      EventType::Suspended              => listOfEvents.push ( "suspended" ),
+      EventType::Resumed                =>
+      {
+        listOfEvents.push ( "resumed" );
+        break;
+      },
+      _                                 => listOfEvents.push ( "other" ),
+    }
+  }
+  myTUI.render();
+  myTUI.turnOffDisplay ( myTerminal ).unwrap();
+  listOfEvents == vec!( "suspended", "resumed" )
+}
+
+//the child is suspended by the key and continued again, the terminal answers the queries while turning on, if asked to.
+//  the keyboard protocol is popped, when suspended, and pushed again, when continued.
+fn suspendByKey
+(
+  flags:                                DisplayFlag,
+  answer:                               &str,
+  key:                                  &[u8],
+  keyboard:                             ( &str, &str ),
+)
+{
+  let ( mut master, input, output )     = openTerminal ( 8, 2 );
+  let child                             = unsafe { libc::fork() };
+  assert!( child >= 0 );
+  if child == 0
+  {
+    unsafe
+    {
+      libc::setpgid ( 0, 0 );
+    }
+    let result                          = panic::catch_unwind ( move || suspendedChild ( flags, input, output ) );
+    unsafe
+    {
+      libc::_exit ( if result.unwrap_or ( false ) { 0 } else { 1 } );
+    }
+  }
+  drop ( input );
+  drop ( output );
+  if !answer.is_empty()
+  {
+    let mut queries                     = String::new();
+    while !queries.contains ( "\x1b[?u\x1b[c" )
+    {
+      queries.push_str ( &readTerminal ( &mut master ) );
+    }
+    master.write_all ( answer.as_bytes() ).unwrap();
+  }
+  thread::sleep ( time::Duration::from_millis ( 200 ) );
+  assert!( readTerminal ( &mut master ).contains ( "abc" ) );
+
+  master.write_all ( key ).unwrap();
+  let mut status                        = 0;
+  assert_eq!( unsafe { libc::waitpid ( child, &mut status, libc::WUNTRACED ) }, child );
+  assert!( unsafe { libc::WIFSTOPPED ( status ) } );
+  assert_eq!( readTerminal ( &mut master ), keyboard.0.to_string() + "\x1b[?1004l\x1b[?2004l\x1b[?1006l\x1b[?1003l\x1b[?25h\x1b[?1049l" );
+
+  unsafe
+  {
+    libc::kill ( child, libc::SIGCONT );
+  }
+  assert_eq!( unsafe { libc::waitpid ( child, &mut status, 0 ) }, child );
+  assert!( unsafe { libc::WIFEXITED ( status ) } );
+  assert_eq!( unsafe { libc::WEXITSTATUS ( status ) }, 0 );
+  let resumed                           = readTerminal ( &mut master );
+  assert!( resumed.starts_with ( &format!( "\x1b[?1049h{}\x1b[2J", keyboard.1 ) ) );
+  assert!( resumed.contains ( "abc" ) );
+}
+
+#[test]
+fn suspendAndResume()
+{
+  suspendByKey ( DisplayFlag::None, "", b"\x1a", ( "", "" ) );
+}
+
+#[test]
+fn suspendByKittyKey()
+{
+  suspendByKey ( DisplayFlag::KittyKeyboard, "\x1b[?0u\x1b[?62;22c", b"\x1b[122;5u", ( "\x1b[<u", "\x1b[>3u" ) );
+}